@@ -8,24 +8,14 @@ function die {
 kFilename="$1"
 [[ -r "$kFilename" ]] || die "cannot read $kFilename"
 
-result_line=$(cargo run --bin=count_sigma -- --input "$kFilename" | grep '^RESULT')
+result_line=$(cargo run --bin=stringology -- stats --max-order 4 --infilename "$kFilename" | grep '^RESULT')
 
 echo "dataset & \$n\$ & \$\\sigma\$ & \$z\$ & \$r\$ & \$H_0\$ & \$H_1\$ & \$H_2\$ & \$H_3\$ & \$H_4\$ \\\\\\"
 
 echo -n $(basename "$kFilename")
-echo -n " & "
-echo -n $result_line | sed 's@.* length=\([0-9]\+\) .*@\1@'
-echo -n " & "
-echo -n $result_line | sed 's@.* sigma=\([0-9]\+\) .*@\1@'
-
-bwtruns=$(cargo run --bin=count_r -- -d --input "$kFilename" | grep '^RESULT' | sed 's@.* bwt_runs=\([0-9]\+\) .*@\1@')
-echo -n " & $bwtruns " 
-lz77factors=$(cargo run --bin=count_z -- --input "$kFilename" | grep '^RESULT' | sed 's@.* factors=\([0-9]\+\).*@\1@')
-echo -n " & $lz77factors " 
-
-for k in $(seq 0 4); do
-	entropy=$(cargo run --bin=entropy -- --order "$k" --input "$kFilename" | grep '^RESULT' | sed 's@.* entropy=\([0-9\.]\+\) .*@\1@')
-	echo -n " & $entropy "
+for key in n sigma z r H_0 H_1 H_2 H_3 H_4; do
+	echo -n " & "
+	echo -n $result_line | sed "s@.* $key=\([0-9\.]\+\) .*@\1@"
 done
 
 echo "\\\\\\"
@@ -0,0 +1,241 @@
+use crate::error::{Error, Result};
+use crate::report;
+use rayon::prelude::*;
+
+/// options for running a tool on several inputs and prefix lengths
+#[derive(clap::Args, Debug, Clone)]
+pub struct BatchArgs {
+    /// further input files, directories (processing all files therein), or glob patterns
+    #[arg(num_args(0..))]
+    pub inputs: Vec<String>,
+
+    /// comma-separated prefix lengths, each processed separately (overrides the prefix length)
+    #[arg(long, value_delimiter = ',')]
+    pub prefixlengths: Vec<usize>,
+
+    /// the number of threads processing the inputs (0: the number of cores)
+    #[arg(short = 'j', long, default_value_t = 0)]
+    pub threads: usize,
+}
+
+/// options for reporting a measure for several prefixes of a text within a single run
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct SweepArgs {
+    /// comma-separated prefix lengths for which the measure is reported
+    #[arg(long, value_delimiter = ',')]
+    pub prefix_steps: Vec<usize>,
+
+    /// report the measure for every prefix whose length is a multiple of this length
+    #[arg(long, conflicts_with = "prefix_steps")]
+    pub linear_steps: Option<usize>,
+
+    /// report the measure for prefixes whose lengths grow by this factor, starting at length 1
+    #[arg(long, conflicts_with_all = ["prefix_steps", "linear_steps"])]
+    pub geometric_steps: Option<f64>,
+}
+
+impl SweepArgs {
+    /// whether the measure is reported for several prefixes
+    pub fn is_enabled(&self) -> bool {
+        !self.prefix_steps.is_empty()
+            || self.linear_steps.is_some()
+            || self.geometric_steps.is_some()
+    }
+
+    /// checks that the linear step is positive and the geometric factor is larger than one
+    pub fn check(&self) -> Result<()> {
+        if self.linear_steps == Some(0) {
+            return Err(Error::InvalidArgument(String::from(
+                "the linear step must be positive",
+            )));
+        }
+        if let Some(factor) = self.geometric_steps {
+            if factor.is_nan() || factor <= 1.0 {
+                return Err(Error::InvalidArgument(format!(
+                    "the geometric factor {} must be larger than one",
+                    factor
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// the smallest prefix length of the sweep that is at least `length`, if any
+    pub fn next_step(&self, length: usize) -> Result<Option<usize>> {
+        self.check()?;
+        Ok(if let Some(step) = self.linear_steps {
+            Some(std::cmp::max(length.div_ceil(step), 1) * step)
+        } else if let Some(factor) = self.geometric_steps {
+            let mut step = 1.0;
+            while (step as usize) < length {
+                step = (step * factor).ceil();
+            }
+            Some(step as usize)
+        } else {
+            self.prefix_steps
+                .iter()
+                .filter(|&&step| step >= length)
+                .min()
+                .copied()
+        })
+    }
+
+    /// The increasing prefix lengths of the sweep for a text of length `n`, where the steps
+    /// beyond `n` are cut down to `n`. Linear and geometric steps always end with `n`, while
+    /// prefix steps end with `n` only if one of them is at least `n`.
+    pub fn steps(&self, n: usize) -> Result<Vec<usize>> {
+        let mut steps = Vec::new();
+        let mut next = self.next_step(0)?;
+        while let Some(step) = next {
+            if step >= n {
+                steps.push(n);
+                break;
+            }
+            steps.push(step);
+            next = self.next_step(step + 1)?;
+        }
+        Ok(steps)
+    }
+}
+
+/// a single run of a tool on an input (or stdin if `None`) truncated to a prefix length
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Job {
+    pub infilename: Option<String>,
+    pub prefixlength: usize,
+}
+
+/// Expands an input argument to the files it denotes: a directory to the files directly in it,
+/// a glob pattern to the paths it matches, each in lexicographic order, and any other argument
+/// to itself.
+pub fn expand_input(input: &str) -> Result<Vec<String>> {
+    let path = std::path::Path::new(input);
+    if path.is_dir() {
+        let directory_error = |source| Error::file(input, source);
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(path).map_err(directory_error)? {
+            let path = entry.map_err(directory_error)?.path();
+            if path.is_file() {
+                files.push(path.to_string_lossy().into_owned());
+            }
+        }
+        files.sort();
+        Ok(files)
+    } else if !path.exists() && input.contains(['*', '?', '[']) {
+        let mut files: Vec<String> = glob::glob(input)
+            .map_err(|e| Error::InvalidArgument(format!("invalid glob pattern {}: {}", input, e)))?
+            .filter_map(std::result::Result::ok)
+            .filter(|path| path.is_file())
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        files.sort();
+        Ok(files)
+    } else {
+        Ok(vec![input.to_string()])
+    }
+}
+
+/// The jobs of a tool called with the input `infilename` and the prefix length `prefixlength`,
+/// extended by the inputs and prefix lengths of `batch`.
+/// The jobs are ordered by input first, and then by prefix length.
+pub fn jobs(
+    infilename: &Option<String>,
+    prefixlength: usize,
+    batch: &BatchArgs,
+) -> Result<Vec<Job>> {
+    let mut inputs: Vec<Option<String>> = infilename.iter().cloned().map(Some).collect();
+    for input in batch.inputs.iter() {
+        inputs.extend(expand_input(input)?.into_iter().map(Some));
+    }
+    if inputs.is_empty() {
+        inputs.push(None);
+    }
+    let prefixlengths = if batch.prefixlengths.is_empty() {
+        vec![prefixlength]
+    } else {
+        batch.prefixlengths.clone()
+    };
+    if inputs[0].is_none() && prefixlengths.len() > 1 {
+        return Err(Error::InvalidArgument(String::from(
+            "the standard input can be read only once; give input files for several prefix lengths",
+        )));
+    }
+    Ok(inputs
+        .into_iter()
+        .flat_map(|infilename| {
+            prefixlengths.iter().map(move |&prefixlength| Job {
+                infilename: infilename.clone(),
+                prefixlength,
+            })
+        })
+        .collect())
+}
+
+/// checks that an output file is only written for a single job
+pub fn check_single_output(jobs: &[Job], outfilename: &Option<String>) -> Result<()> {
+    if jobs.len() > 1 && outfilename.is_some() {
+        return Err(Error::InvalidArgument(String::from(
+            "an output file can only be written for a single input and prefix length",
+        )));
+    }
+    Ok(())
+}
+
+/// Runs `f` on all jobs with `threads` threads (0: the number of cores), and returns the
+/// result records of all jobs in the order of the jobs, or an error of a failing job (the first
+/// one if the jobs run one after another). If the jobs run one after another, records without
+/// peak memory are assigned the peak memory of the process during the job, after resetting the
+/// peak before the job; otherwise, or if the peak cannot be reset after the first job, the peak
+/// memory would include other jobs and is left unset.
+pub fn run<F>(jobs: &[Job], threads: usize, f: F) -> Result<Vec<report::ResultRecord>>
+where
+    F: Fn(&Job) -> Result<Vec<report::ResultRecord>> + Sync,
+{
+    let sequential = jobs.len() == 1 || threads == 1;
+    let run_job = |(index, job): (usize, &Job)| {
+        let measured = sequential && (report::reset_peak_memory() || index == 0);
+        let mut records = f(job)?;
+        for record in records.iter_mut() {
+            if measured && record.peak_memory_kb.is_none() {
+                record.peak_memory_kb = report::peak_memory_kb();
+            }
+        }
+        Ok(records)
+    };
+    let records: Vec<Vec<report::ResultRecord>> = if sequential {
+        jobs.iter()
+            .enumerate()
+            .map(run_job)
+            .collect::<Result<_>>()?
+    } else {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .map_err(|e| Error::InvalidArgument(format!("cannot create the thread pool: {}", e)))?;
+        pool.install(|| {
+            jobs.par_iter()
+                .enumerate()
+                .map(run_job)
+                .collect::<Result<_>>()
+        })?
+    };
+    Ok(records.into_iter().flatten().collect())
+}
+
+/// runs `f` on all jobs like [`run`], and prints their result records in the given format
+pub fn run_and_report<F>(
+    jobs: &[Job],
+    threads: usize,
+    format: report::OutputFormat,
+    f: F,
+) -> Result<()>
+where
+    F: Fn(&Job) -> Result<Vec<report::ResultRecord>> + Sync,
+{
+    //@ `run` assigns the peak memory where it is the peak memory of a single job
+    let mut reporter = report::Reporter::new(format).without_peak_memory();
+    for record in run(jobs, threads, f)? {
+        reporter.emit(record);
+    }
+    Ok(())
+}
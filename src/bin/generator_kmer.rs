@@ -1,23 +1,7 @@
-use generator::{done, Gn};
-use std::str;
-
+//! alias of `stringology generator_kmer`, kept for scripts calling the tool directly
 fn main() {
-    let length: usize = 8;
-    let alphabet_size: usize = 4;
-    let g = Gn::new_scoped(|mut s| {
-        for num in 0..usize::pow(alphabet_size, length as u32) {
-            let mut text = vec![b'a'; length];
-            let mut remainder = num;
-            for letter in &mut text {
-                *letter = b'a' + ((remainder % alphabet_size) as u8);
-                remainder /= alphabet_size;
-            }
-            s.yield_(text);
-        }
-        done!();
-    });
-
-    for i in g {
-        println!("{}", str::from_utf8(i.as_slice()).unwrap());
-    }
+    stringology::tools::run_alias(
+        env!("CARGO_BIN_NAME"),
+        stringology::tools::generator_kmer::run,
+    );
 }
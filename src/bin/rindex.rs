@@ -0,0 +1,4 @@
+//! alias of `stringology rindex`, kept for scripts calling the tool directly
+fn main() {
+    stringology::tools::run_alias(env!("CARGO_BIN_NAME"), stringology::tools::rindex::run);
+}
@@ -0,0 +1,109 @@
+use clap::{CommandFactory, Parser, Subcommand};
+use stringology::tools;
+
+/// a collection of stringology tools computing text indexes, factorizations and measures
+#[derive(Parser, Debug)]
+#[command(author, version, about, long_about = None)]
+struct Cli {
+    #[command(flatten)]
+    global: tools::GlobalArgs,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    Bwt(tools::bwt::Args),
+    #[command(visible_alias = "r", alias = "count_r")]
+    BwtRuns(tools::count_r::Args),
+    #[command(visible_alias = "z", alias = "count_z")]
+    Lz77(tools::count_z::Args),
+    #[command(alias = "count_lexparse")]
+    Lexparse(tools::count_lexparse::Args),
+    #[command(alias = "count_sigma")]
+    Sigma(tools::count_sigma::Args),
+    #[command(alias = "count_runs")]
+    Runs(tools::count_runs::Args),
+    Entropy(tools::entropy::Args),
+    #[command(alias = "entropy_kmer")]
+    EntropyKmer(tools::entropy_kmer::Args),
+    Stats(tools::stats::Args),
+    Enumerate(tools::enumerate::Args),
+    #[command(alias = "enumerate_lexparse")]
+    EnumerateLexparse(tools::enumerate_lexparse::Args),
+    Escape(tools::escape::Args),
+    #[command(alias = "fm_search")]
+    FmSearch(tools::fm_search::Args),
+    #[command(alias = "generator_kmer")]
+    Kmers(tools::generator_kmer::Args),
+    #[command(alias = "is_stringattractor")]
+    Attractor(tools::is_stringattractor::Args),
+    #[command(alias = "lyndonfactorization")]
+    Lyndon(tools::lyndonfactorization::Args),
+    #[command(alias = "lyndonwords")]
+    LyndonWords(tools::lyndonwords::Args),
+    Mtf(tools::mtf::Args),
+    Mus(tools::mus::Args),
+    #[command(alias = "randomstring")]
+    Random(tools::randomstring::Args),
+    Reverse(tools::reverse::Args),
+    Rindex(tools::rindex::Args),
+    Rle(tools::rle::Args),
+    Search(tools::search::Args),
+    Sensitivity(tools::sensitivity::Args),
+    #[command(visible_alias = "dawg", alias = "suffixautomaton")]
+    SuffixAutomaton(tools::suffixautomaton::Args),
+    Word(tools::word::Args),
+    /// prints the completion script of a shell
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let global = &cli.global;
+    global.init_logging();
+    let result = match &cli.command {
+        Command::Bwt(args) => tools::bwt::run(global, args),
+        Command::BwtRuns(args) => tools::count_r::run(global, args),
+        Command::Lz77(args) => tools::count_z::run(global, args),
+        Command::Lexparse(args) => tools::count_lexparse::run(global, args),
+        Command::Sigma(args) => tools::count_sigma::run(global, args),
+        Command::Runs(args) => tools::count_runs::run(global, args),
+        Command::Entropy(args) => tools::entropy::run(global, args),
+        Command::EntropyKmer(args) => tools::entropy_kmer::run(global, args),
+        Command::Stats(args) => tools::stats::run(global, args),
+        Command::Enumerate(args) => tools::enumerate::run(global, args),
+        Command::EnumerateLexparse(args) => tools::enumerate_lexparse::run(global, args),
+        Command::Escape(args) => tools::escape::run(global, args),
+        Command::FmSearch(args) => tools::fm_search::run(global, args),
+        Command::Kmers(args) => tools::generator_kmer::run(global, args),
+        Command::Attractor(args) => tools::is_stringattractor::run(global, args),
+        Command::Lyndon(args) => tools::lyndonfactorization::run(global, args),
+        Command::LyndonWords(args) => tools::lyndonwords::run(global, args),
+        Command::Mtf(args) => tools::mtf::run(global, args),
+        Command::Mus(args) => tools::mus::run(global, args),
+        Command::Random(args) => tools::randomstring::run(global, args),
+        Command::Reverse(args) => tools::reverse::run(global, args),
+        Command::Rindex(args) => tools::rindex::run(global, args),
+        Command::Rle(args) => tools::rle::run(global, args),
+        Command::Search(args) => tools::search::run(global, args),
+        Command::Sensitivity(args) => tools::sensitivity::run(global, args),
+        Command::SuffixAutomaton(args) => tools::suffixautomaton::run(global, args),
+        Command::Word(args) => tools::word::run(global, args),
+        Command::Completions { shell } => {
+            let mut command = Cli::command();
+            clap_complete::generate(*shell, &mut command, "stringology", &mut std::io::stdout());
+            Ok(())
+        }
+    };
+    tools::exit_on_error("stringology", result);
+}
+
+#[test]
+fn test_cli() {
+    Cli::command().debug_assert();
+}
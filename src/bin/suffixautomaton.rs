@@ -0,0 +1,7 @@
+//! alias of `stringology suffixautomaton`, kept for scripts calling the tool directly
+fn main() {
+    stringology::tools::run_alias(
+        env!("CARGO_BIN_NAME"),
+        stringology::tools::suffixautomaton::run,
+    );
+}
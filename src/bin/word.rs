@@ -1,57 +1,4 @@
-use stringology::core;
-use stringology::io;
-use stringology::word;
-
-extern crate clap;
-use clap::Parser;
-
-/// computes a recurrent word
-#[derive(Parser, Debug)]
-#[command(author, version, about, long_about = None)]
-struct Args {
-    /// the input file to read (otherwise read from stdin)
-    #[arg(short)]
-    k: u8,
-
-    #[arg(short, long, value_enum)]
-    name: WordName,
-
-    /// the output file to write (otherwise write from stdout)
-    #[arg(short, long)]
-    outfilename: Option<String>,
-}
-
-#[derive(clap::ValueEnum, Clone, Debug)]
-enum WordName {
-    Fibonacci,
-    FibonacciLyndonFactor,
-    Tribonacci,
-    Kolakoski,
-    ThueMorse,
-    PeriodDoubling,
-    PaperFolding,
-    QuaternaryPaperFolding,
-    BinaryDeBrujin,
-    Power2,
-    Vtm,
-}
-
+//! alias of `stringology word`, kept for scripts calling the tool directly
 fn main() {
-    let args = Args::parse();
-    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&args.outfilename));
-
-    let fun = match args.name {
-        WordName::Fibonacci => word::fibonacci_word,
-        WordName::FibonacciLyndonFactor => word::fibonacci_lyndonfactor,
-        WordName::Tribonacci => word::tribonacci_word,
-        WordName::Kolakoski => word::kolakoski_word,
-        WordName::ThueMorse => word::thuemorse_word,
-        WordName::PeriodDoubling => word::period_doubling_word,
-        WordName::PaperFolding => word::paperfolding_word,
-        WordName::QuaternaryPaperFolding => word::quaternary_paperfolding_word,
-        WordName::BinaryDeBrujin => word::binary_debruijn_word,
-        WordName::Power2 => word::power2_sequence,
-        WordName::Vtm => word::vtm_word,
-    };
-    writer.write_all(fun(args.k).as_slice()).unwrap();
+    stringology::tools::run_alias(env!("CARGO_BIN_NAME"), stringology::tools::word::run);
 }
@@ -1,3 +1,4 @@
+use crate::error::{Error, Result};
 use crate::io;
 use num::cast::AsPrimitive;
 extern crate cdivsufsort;
@@ -7,14 +8,22 @@ use more_asserts::assert_gt;
 use more_asserts::assert_lt;
 use more_asserts::debug_assert_lt;
 
-pub fn bwt_from_text_by_sa(text: &Vec<u8>) -> Vec<u8> {
-    assert_gt!(!text.len(), 0);
+/// Computes the BWT of a text ending with a unique 0 byte via its suffix array.
+/// Fails if the text is empty or contains another 0 byte.
+pub fn bwt_from_text_by_sa(text: &Vec<u8>) -> Result<Vec<u8>> {
+    if text.is_empty() {
+        return Err(Error::InvalidInput(String::from(
+            "the BWT of an empty text is not defined",
+        )));
+    }
     let n = text.len();
     let mut sa = vec![0; n];
-    assert!(
-        !text[..text.len() - 1].iter().any(|&x| x == 0),
-        "the input text contains bytes equal to zero!"
-    );
+    if let Some(position) = text[..text.len() - 1].iter().position(|&c| c == 0) {
+        return Err(Error::InvalidInput(format!(
+            "the input text contains a byte equal to zero at position {}",
+            position
+        )));
+    }
     cdivsufsort::sort_in_place(text, sa.as_mut_slice());
     let mut bwt = vec![text[0]; n];
     // let mut rsa = vec![0; n];
@@ -26,7 +35,7 @@ pub fn bwt_from_text_by_sa(text: &Vec<u8>) -> Vec<u8> {
     debug!("bwt: {:?}", bwt);
     debug!("sa: {:?}", sa);
     // println!("rsa: {:?}", rsa);
-    bwt
+    Ok(bwt)
 }
 
 /// computes the rightmost column of the BWT matrix
@@ -57,9 +66,13 @@ pub fn bwt_by_matrix_naive<T: std::cmp::Ord + Copy>(text: &[T]) -> Vec<T> {
 /// input text by first finding its Lyndon conjugate, appending a 0 byte,
 /// then computing the BWT of this conjugate
 /// via the suffix array, and removing the 0 byte at the end.
-pub fn bwt_by_matrix(text: &[u8]) -> Vec<u8> {
+pub fn bwt_by_matrix(text: &[u8]) -> Result<Vec<u8>> {
     let n = text.len();
-    assert_gt!(n, 0);
+    if n == 0 {
+        return Err(Error::InvalidInput(String::from(
+            "the BWT of an empty text is not defined",
+        )));
+    }
 
     let conjugate_start = lyndon_conjugate(text);
     let mut newtext = Vec::with_capacity(n);
@@ -70,9 +83,9 @@ pub fn bwt_by_matrix(text: &[u8]) -> Vec<u8> {
         newtext.push(*letter);
     }
     newtext.push(0u8);
-    let mut bwt = bwt_from_text_by_sa(&newtext);
+    let mut bwt = bwt_from_text_by_sa(&newtext)?;
     bwt.remove(bwt.iter().position(|&x| x == 0).unwrap());
-    bwt
+    Ok(bwt)
 }
 
 /// the conjugate of the text that is Lyndon
@@ -103,7 +116,7 @@ pub fn is_primitive<C: Eq>(text: &[C]) -> bool {
     if period == text.len() {
         return true;
     }
-    (text.len() % smallest_period(&border)) != 0
+    !text.len().is_multiple_of(smallest_period(&border))
 }
 
 /// the smallest period of a string is given by the  last entry of its border array
@@ -194,6 +207,123 @@ pub fn suffixarray_naive<C: Ord>(text: &[C]) -> Vec<usize> {
     sa
 }
 
+/// Computes the suffix array of a text over an arbitrary ordered alphabet by prefix doubling.
+/// The suffixes are first sorted by their first characters with a comparison sort; the k-th round
+/// then sorts them by their first 2^k characters with a counting sort on the pairs of ranks of the
+/// previous round. Like divsufsort, a suffix is smaller than all other suffixes it is a proper
+/// prefix of. Fails if the text is too long for 32-bit suffix array entries.
+/// Reference: Udi Manber, Gene Myers: "Suffix Arrays: A New Method for On-Line String
+/// Searches". SIAM J. Comput. 22(5): 935-948 (1993)
+pub fn suffixarray_prefix_doubling<C: Ord>(text: &[C]) -> Result<Vec<i32>> {
+    let n = text.len();
+    if n > i32::MAX as usize {
+        return Err(Error::InvalidInput(format!(
+            "the text of length {} is too long for 32-bit suffix array entries",
+            n
+        )));
+    }
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let mut sa: Vec<usize> = (0..n).collect();
+    sa.sort_by(|&a, &b| text[a].cmp(&text[b]));
+    //@ rank[i] is the rank of the suffix starting at i among the sorted prefixes
+    let mut rank = vec![0_usize; n];
+    for i in 1..n {
+        rank[sa[i]] = rank[sa[i - 1]] + (text[sa[i]] != text[sa[i - 1]]) as usize;
+    }
+    let mut new_rank = vec![0_usize; n];
+    let mut second_order = Vec::with_capacity(n);
+    let mut counts = vec![0_usize; n + 1];
+    let mut k = 1;
+    while k < n && rank[sa[n - 1]] < n - 1 {
+        //@ sort by the rank of the suffix starting k positions later, where suffixes shorter
+        //@ than k come first
+        let second_key = |i: usize, rank: &[usize]| if i + k < n { rank[i + k] + 1 } else { 0 };
+        second_order.clear();
+        second_order.extend(n - k..n);
+        second_order.extend(sa.iter().filter(|&&i| i >= k).map(|&i| i - k));
+        //@ stable counting sort by the rank of the first k characters
+        counts.iter_mut().for_each(|c| *c = 0);
+        for &i in second_order.iter() {
+            counts[rank[i] + 1] += 1;
+        }
+        for c in 1..counts.len() {
+            counts[c] += counts[c - 1];
+        }
+        for &i in second_order.iter() {
+            sa[counts[rank[i]]] = i;
+            counts[rank[i]] += 1;
+        }
+        new_rank[sa[0]] = 0;
+        for j in 1..n {
+            let (a, b) = (sa[j - 1], sa[j]);
+            let differ = rank[a] != rank[b] || second_key(a, &rank) != second_key(b, &rank);
+            new_rank[b] = new_rank[a] + differ as usize;
+        }
+        std::mem::swap(&mut rank, &mut new_rank);
+        k *= 2;
+    }
+    Ok(sa.into_iter().map(|i| i as i32).collect())
+}
+
+/// Increments each symbol of an integer text and appends a 0 acting as the dollar sign,
+/// which is then smaller than all other symbols.
+/// Fails if the text contains the symbol u64::MAX.
+pub fn integer_text_with_sentinel(symbols: &[u64]) -> Result<Vec<u64>> {
+    let mut text: Vec<u64> = symbols
+        .iter()
+        .map(|&c| {
+            c.checked_add(1).ok_or_else(|| {
+                Error::InvalidInput(String::from("the input text contains the symbol u64::MAX"))
+            })
+        })
+        .collect::<Result<_>>()?;
+    text.push(0);
+    Ok(text)
+}
+
+/// Computes the BWT of an integer text ending with a unique 0 symbol via its suffix array.
+/// Fails if the text is empty or contains another 0 symbol.
+pub fn bwt_from_integer_text_by_sa(text: &[u64]) -> Result<Vec<u64>> {
+    if text.is_empty() {
+        return Err(Error::InvalidInput(String::from(
+            "the BWT of an empty text is not defined",
+        )));
+    }
+    if let Some(position) = text[..text.len() - 1].iter().position(|&c| c == 0) {
+        return Err(Error::InvalidInput(format!(
+            "the input text contains a symbol equal to zero at position {}",
+            position
+        )));
+    }
+    let sa: Vec<usize> = suffixarray_prefix_doubling(text)?
+        .into_iter()
+        .map(|x| x as usize)
+        .collect();
+    Ok(bwt_from_sa(text, &sa))
+}
+
+/// the integer counterpart of `bwt_by_matrix`, computing the BWT of the Lyndon conjugate with
+/// a sentinel appended
+pub fn bwt_by_matrix_integer(text: &[u64]) -> Result<Vec<u64>> {
+    let n = text.len();
+    if n == 0 {
+        return Err(Error::InvalidInput(String::from(
+            "the BWT of an empty text is not defined",
+        )));
+    }
+    let conjugate_start = lyndon_conjugate(text);
+    let conjugate: Vec<u64> = text[conjugate_start..]
+        .iter()
+        .chain(text[..conjugate_start].iter())
+        .copied()
+        .collect();
+    let mut bwt = bwt_from_integer_text_by_sa(&integer_text_with_sentinel(&conjugate)?)?;
+    bwt.remove(bwt.iter().position(|&x| x == 0).unwrap());
+    Ok(bwt.iter().map(|&c| c - 1).collect())
+}
+
 /**
  * bwt is a permutation of `text` based on `sa`
  */
@@ -254,7 +384,7 @@ pub fn inverse_permutation<T: AsPrimitive<usize> + num::cast::FromPrimitive>(arr
     inv
 }
 
-pub fn compute_plcp(text: &[u8], phi: &[i32]) -> Vec<u32> {
+pub fn compute_plcp<C: Eq>(text: &[C], phi: &[i32]) -> Vec<u32> {
     debug_assert_eq!(text.len(), phi.len());
     let mut plcp = vec![0; text.len()];
     let mut length: usize = 0;
@@ -354,6 +484,60 @@ pub fn duval<C: Ord>(text: &[C]) -> Vec<usize> {
     ending_positions
 }
 
+/// The number of Lyndon factors of each prefix of `text` whose length is in the increasing
+/// sequence `lengths`, computed online by a single run of Duval's algorithm.
+/// When Duval's algorithm reads the last character of a prefix for the first time, the prefix
+/// consists of the factors already emitted, followed by w^e w' for a Lyndon word w and a proper
+/// prefix w' of w, such that its factorization ends with e times w and the factorization of w'.
+pub fn lyndon_factor_counts_of_prefixes<C: Ord>(text: &[C], lengths: &[usize]) -> Vec<usize> {
+    let mut counts = Vec::with_capacity(lengths.len());
+    let mut next_length = lengths.iter().peekable();
+    while next_length.next_if_eq(&&0).is_some() {
+        counts.push(0);
+    }
+    let mut factors = 0;
+    let mut reached = 0; //@ the length of the longest prefix read so far
+    let mut k = 0;
+    let n = text.len();
+    while k < n {
+        let mut i = k;
+        let mut j = k + 1;
+        loop {
+            if j > reached {
+                reached = j;
+                if next_length.next_if_eq(&&j).is_some() {
+                    let period = j - i;
+                    let repetitions = (j - k) / period;
+                    let tail = &text[k + repetitions * period..j];
+                    counts.push(factors + repetitions + duval(tail).len());
+                }
+            }
+            if j == n || text[i] > text[j] {
+                break;
+            }
+            if text[i] < text[j] {
+                i = k;
+            } else {
+                i += 1;
+            }
+            j += 1;
+        }
+        loop {
+            assert_lt!(i, j);
+            k += j - i;
+            factors += 1;
+            if k >= i {
+                break;
+            }
+        }
+    }
+    assert!(
+        next_length.next().is_none(),
+        "the prefix lengths must be increasing and at most the text length"
+    );
+    counts
+}
+
 /// Lyndon factorization via the inverse suffix array
 pub fn isa_lyndon_factorization(isa: &[i32]) -> Vec<usize> {
     let mut ending_positions = Vec::new();
@@ -551,27 +735,27 @@ impl<C: Eq + Copy + Clone> Iterator for ConjugateIterator<'_, C> {
     }
 }
 
-/// counts the number of runs in an array `arr`
-pub fn number_of_runs<R: std::io::Read>(reader: &mut R) -> usize {
-    match io::read_char(reader) {
-        Err(_) => 0,
-        Ok(first_char) => {
-            let mut run_counter = 1; //@ counts the number of character runs
-            let mut prev_char = first_char; //@ the current character of the chracter run
-            loop {
-                match io::read_char(reader) {
-                    Err(_) => break,
-                    Ok(next_char) => {
-                        if next_char != prev_char {
-                            prev_char = next_char;
-                            run_counter += 1;
-                        }
-                    }
-                }
-            }
-            run_counter
+/// counts the number of character runs in the stream read by `reader`
+pub fn number_of_runs<R: std::io::Read>(reader: &mut R) -> Result<usize> {
+    let mut run_counter = 0; //@ counts the number of character runs
+    let mut prev_char = None; //@ the current character of the chracter run
+    for next_char in io::ByteReader::new(reader) {
+        let next_char = next_char?;
+        if prev_char != Some(next_char) {
+            prev_char = Some(next_char);
+            run_counter += 1;
         }
     }
+    Ok(run_counter)
+}
+
+/// counts the number of runs in a slice `arr`
+pub fn number_of_runs_in<C: Eq>(arr: &[C]) -> usize {
+    if arr.is_empty() {
+        0
+    } else {
+        1 + arr.windows(2).filter(|w| w[0] != w[1]).count()
+    }
 }
 
 /// converts &Option<String> to Option<&str>
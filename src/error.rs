@@ -0,0 +1,64 @@
+/// the errors reported by the library and the tools
+#[derive(Debug)]
+pub enum Error {
+    /// reading from or writing to a stream failed
+    Io(std::io::Error),
+    /// the file `path` could not be opened, read, or created
+    File {
+        path: String,
+        source: std::io::Error,
+    },
+    /// the input violates a requirement of an algorithm, such as a zero byte in a text whose
+    /// BWT is computed
+    InvalidInput(String),
+    /// the given arguments are inconsistent or out of range
+    InvalidArgument(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// the error of the file `path` caused by `source`
+    pub fn file(path: &str, source: std::io::Error) -> Error {
+        Error::File {
+            path: path.to_string(),
+            source,
+        }
+    }
+
+    /// The exit code of a tool failing with this error, following the BSD sysexits:
+    /// 64 for invalid arguments, 65 for invalid input data, and 74 for I/O errors.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::InvalidArgument(_) => 64,
+            Error::InvalidInput(_) => 65,
+            Error::Io(_) | Error::File { .. } => 74,
+        }
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io(source) => write!(f, "{}", source),
+            Error::File { path, source } => write!(f, "{}: {}", path, source),
+            Error::InvalidInput(message) => write!(f, "invalid input: {}", message),
+            Error::InvalidArgument(message) => write!(f, "invalid argument: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(source) | Error::File { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(error: std::io::Error) -> Error {
+        Error::Io(error)
+    }
+}
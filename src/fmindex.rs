@@ -0,0 +1,430 @@
+extern crate cdivsufsort;
+use crate::error::{Error, Result};
+use succinct::rank::BitRankSupport;
+use succinct::{BitVecMut, BitVector, Rank9, SpaceUsage};
+
+/// rank and access support on the BWT
+pub trait ByteRank {
+    /// builds the data structure on `bwt`
+    fn from_bwt(bwt: &[u8]) -> Self;
+    /// number of occurrences of `c` in bwt[0..i)
+    fn rank(&self, c: u8, i: usize) -> usize;
+    /// the character bwt[i]
+    fn access(&self, i: usize) -> u8;
+    /// length of the BWT
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// space of the data structure in bytes
+    fn size_in_bytes(&self) -> usize;
+}
+
+/// the number of ones in bits[0..i)
+pub fn rank1_exclusive(bits: &Rank9<BitVector<u64>>, i: usize) -> usize {
+    if i == 0 {
+        0
+    } else {
+        bits.rank1(i as u64 - 1) as usize
+    }
+}
+
+/// Stores the BWT in plain form plus, for every `SAMPLE_RATE`-th position, the number of
+/// occurrences of each character of the effective alphabet before that position.
+pub struct SampledOccurrences {
+    bwt: Vec<u8>,
+    /// maps a byte to its rank in the effective alphabet, or u8::MAX as usize + 1 if it does not occur
+    alphabet_map: Vec<usize>,
+    sigma: usize,
+    samples: Vec<u32>,
+}
+
+impl SampledOccurrences {
+    const SAMPLE_RATE: usize = 64;
+}
+
+impl ByteRank for SampledOccurrences {
+    fn from_bwt(bwt: &[u8]) -> Self {
+        let mut alphabet_map = vec![u8::MAX as usize + 1; u8::MAX as usize + 1];
+        let mut sigma = 0;
+        for &c in bwt {
+            if alphabet_map[c as usize] > u8::MAX as usize {
+                alphabet_map[c as usize] = 0;
+            }
+        }
+        for entry in alphabet_map.iter_mut() {
+            if *entry == 0 {
+                *entry = sigma;
+                sigma += 1;
+            }
+        }
+        let blocks = bwt.len() / Self::SAMPLE_RATE + 1;
+        let mut samples = vec![0_u32; blocks * sigma];
+        let mut counts = vec![0_u32; sigma];
+        for block in 0..blocks {
+            samples[block * sigma..(block + 1) * sigma].copy_from_slice(&counts);
+            let end = std::cmp::min(bwt.len(), (block + 1) * Self::SAMPLE_RATE);
+            for &c in bwt[block * Self::SAMPLE_RATE..end].iter() {
+                counts[alphabet_map[c as usize]] += 1;
+            }
+        }
+        SampledOccurrences {
+            bwt: bwt.to_vec(),
+            alphabet_map,
+            sigma,
+            samples,
+        }
+    }
+
+    fn rank(&self, c: u8, i: usize) -> usize {
+        let character = self.alphabet_map[c as usize];
+        if character >= self.sigma {
+            return 0;
+        }
+        let block = i / Self::SAMPLE_RATE;
+        let block_start = block * Self::SAMPLE_RATE;
+        self.samples[block * self.sigma + character] as usize
+            + self.bwt[block_start..i].iter().filter(|&&x| x == c).count()
+    }
+
+    fn access(&self, i: usize) -> u8 {
+        self.bwt[i]
+    }
+
+    fn len(&self) -> usize {
+        self.bwt.len()
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.bwt.len()
+            + self.alphabet_map.len() * std::mem::size_of::<usize>()
+            + self.samples.len() * std::mem::size_of::<u32>()
+    }
+}
+
+/// A level-wise wavelet tree on bytes, storing a bit vector with rank support for each of the
+/// eight levels.
+pub struct ByteWaveletTree {
+    levels: Vec<Rank9<BitVector<u64>>>,
+    /// node_begin[l][p] is the starting position of the node at level `l` whose path is given by
+    /// the `l` bits of `p`
+    node_begin: Vec<Vec<usize>>,
+    length: usize,
+}
+
+impl ByteWaveletTree {
+    const LEVELS: usize = 8;
+}
+
+impl ByteRank for ByteWaveletTree {
+    fn from_bwt(bwt: &[u8]) -> Self {
+        let mut counts = vec![0_usize; u8::MAX as usize + 1];
+        for &c in bwt {
+            counts[c as usize] += 1;
+        }
+        let mut node_begin = Vec::with_capacity(Self::LEVELS);
+        for level in 0..Self::LEVELS {
+            let mut begin = vec![0_usize; 1 << level];
+            let mut sum = 0;
+            for (prefix, entry) in begin.iter_mut().enumerate() {
+                *entry = sum;
+                let width = 1 << (Self::LEVELS - level);
+                sum += counts[prefix * width..(prefix + 1) * width]
+                    .iter()
+                    .sum::<usize>();
+            }
+            node_begin.push(begin);
+        }
+
+        let mut levels = Vec::with_capacity(Self::LEVELS);
+        let mut current = bwt.to_vec();
+        for level in 0..Self::LEVELS {
+            let shift = Self::LEVELS - 1 - level;
+            let mut bits = BitVector::with_fill(current.len() as u64, false);
+            for (i, &c) in current.iter().enumerate() {
+                if (c >> shift) & 1 == 1 {
+                    bits.set_bit(i as u64, true);
+                }
+            }
+            levels.push(Rank9::new(bits));
+            //@ stably sort by the first level+1 bits to obtain the sequence of the next level
+            current.sort_by_key(|&c| c >> shift);
+        }
+        ByteWaveletTree {
+            levels,
+            node_begin,
+            length: bwt.len(),
+        }
+    }
+
+    fn rank(&self, c: u8, i: usize) -> usize {
+        let mut prefix = 0;
+        let mut position = i;
+        for (level, bits) in self.levels.iter().enumerate() {
+            let begin = self.node_begin[level][prefix];
+            let ones_before = rank1_exclusive(bits, begin);
+            let ones = rank1_exclusive(bits, begin + position) - ones_before;
+            let bit = ((c >> (Self::LEVELS - 1 - level)) & 1) as usize;
+            position = if bit == 1 { ones } else { position - ones };
+            prefix = 2 * prefix + bit;
+        }
+        position
+    }
+
+    fn access(&self, i: usize) -> u8 {
+        use succinct::BitVec;
+        let mut prefix = 0;
+        let mut position = i;
+        for (level, bits) in self.levels.iter().enumerate() {
+            let begin = self.node_begin[level][prefix];
+            let ones_before = rank1_exclusive(bits, begin);
+            let ones = rank1_exclusive(bits, begin + position) - ones_before;
+            let bit = bits.get_bit((begin + position) as u64) as usize;
+            position = if bit == 1 { ones } else { position - ones };
+            prefix = 2 * prefix + bit;
+        }
+        prefix as u8
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.levels.iter().map(|l| l.total_bytes()).sum::<usize>()
+            + self
+                .node_begin
+                .iter()
+                .map(|l| l.len() * std::mem::size_of::<usize>())
+                .sum::<usize>()
+    }
+}
+
+/// The FM-index of a text, consisting of the BWT with rank support, the C array, and samples of
+/// the suffix array and its inverse for locating and extracting.
+/// A zero byte is appended as sentinel to the text, which therefore must not contain zero bytes.
+/// Reference: Paolo Ferragina, Giovanni Manzini: "Opportunistic Data Structures with
+/// Applications". FOCS 2000: 390-398
+pub struct FMIndex<R: ByteRank> {
+    rank: R,
+    /// c_array[c] is the number of characters in the text smaller than c
+    c_array: Vec<usize>,
+    /// marks the BWT positions whose suffix array values are sampled
+    sampled: Rank9<BitVector<u64>>,
+    /// suffix array values that are divisible by `sample_rate`, in suffix array order
+    sa_samples: Vec<u32>,
+    /// isa_samples[k] is the inverse suffix array value of text position k * sample_rate
+    isa_samples: Vec<u32>,
+    sample_rate: usize,
+}
+
+impl<R: ByteRank> FMIndex<R> {
+    /// builds the FM-index of `text`, sampling every `sample_rate`-th text position
+    pub fn new(text: &[u8], sample_rate: usize) -> Result<FMIndex<R>> {
+        if sample_rate == 0 {
+            return Err(Error::InvalidArgument(String::from(
+                "the sample rate must be positive",
+            )));
+        }
+        if text.contains(&0) {
+            return Err(Error::InvalidInput(String::from(
+                "the input text contains bytes equal to zero",
+            )));
+        }
+        let mut text = text.to_vec();
+        text.push(0u8);
+        let n = text.len();
+        let sa = {
+            let mut sa = vec![0; n];
+            cdivsufsort::sort_in_place(&text, sa.as_mut_slice());
+            sa
+        };
+        let bwt: Vec<u8> = sa
+            .iter()
+            .map(|&value| text[(n + value as usize - 1) % n])
+            .collect();
+
+        let mut c_array = vec![0_usize; u8::MAX as usize + 2];
+        for &c in text.iter() {
+            c_array[c as usize + 1] += 1;
+        }
+        for c in 1..c_array.len() {
+            c_array[c] += c_array[c - 1];
+        }
+
+        let mut sampled = BitVector::with_fill(n as u64, false);
+        let mut sa_samples = Vec::new();
+        let mut isa_samples = vec![0_u32; (n - 1) / sample_rate + 1];
+        for (i, &value) in sa.iter().enumerate() {
+            let value = value as usize;
+            if value.is_multiple_of(sample_rate) {
+                sampled.set_bit(i as u64, true);
+                sa_samples.push(value as u32);
+                isa_samples[value / sample_rate] = i as u32;
+            }
+        }
+        Ok(FMIndex {
+            rank: R::from_bwt(&bwt),
+            c_array,
+            sampled: Rank9::new(sampled),
+            sa_samples,
+            isa_samples,
+            sample_rate,
+        })
+    }
+
+    /// length of the indexed text, including the sentinel
+    pub fn len(&self) -> usize {
+        self.rank.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rank.is_empty()
+    }
+
+    /// the character at BWT position `i`
+    pub fn bwt_access(&self, i: usize) -> u8 {
+        self.rank.access(i)
+    }
+
+    /// the LF mapping of BWT position `i`
+    fn lf(&self, i: usize) -> usize {
+        let c = self.rank.access(i);
+        self.c_array[c as usize] + self.rank.rank(c, i)
+    }
+
+    /// the suffix array interval [begin, end) of all suffixes prefixed by `pattern`
+    pub fn backward_search(&self, pattern: &[u8]) -> (usize, usize) {
+        let mut begin = 0;
+        let mut end = self.len();
+        for &c in pattern.iter().rev() {
+            begin = self.c_array[c as usize] + self.rank.rank(c, begin);
+            end = self.c_array[c as usize] + self.rank.rank(c, end);
+            if begin >= end {
+                return (0, 0);
+            }
+        }
+        (begin, end)
+    }
+
+    /// number of occurrences of `pattern` in the text
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        let (begin, end) = self.backward_search(pattern);
+        end - begin
+    }
+
+    /// the suffix array value at position `i`, obtained by LF steps until reaching a sample
+    pub fn suffix_array_value(&self, i: usize) -> usize {
+        use succinct::BitVec;
+        let mut position = i;
+        let mut steps = 0;
+        while !self.sampled.get_bit(position as u64) {
+            position = self.lf(position);
+            steps += 1;
+        }
+        self.sa_samples[rank1_exclusive(&self.sampled, position)] as usize + steps
+    }
+
+    /// the starting positions of all occurrences of `pattern` in the text, in suffix array order
+    pub fn locate(&self, pattern: &[u8]) -> Vec<usize> {
+        let (begin, end) = self.backward_search(pattern);
+        (begin..end).map(|i| self.suffix_array_value(i)).collect()
+    }
+
+    /// the substring text[i..i+length), or None if it exceeds the text
+    pub fn extract(&self, i: usize, length: usize) -> Option<Vec<u8>> {
+        let text_length = self.len() - 1;
+        if i.checked_add(length)? > text_length {
+            return None;
+        }
+        //@ start at the next sampled text position to the right, or at the sentinel
+        let mut position = i + length;
+        let mut row = if position.div_ceil(self.sample_rate) * self.sample_rate >= text_length {
+            position = text_length;
+            0 //@ the sentinel suffix is the lexicographically smallest
+        } else {
+            position = position.div_ceil(self.sample_rate) * self.sample_rate;
+            self.isa_samples[position / self.sample_rate] as usize
+        };
+        let mut output = Vec::with_capacity(length);
+        while position > i {
+            let c = self.rank.access(row);
+            position -= 1;
+            if position < i + length {
+                output.push(c);
+            }
+            row = self.c_array[c as usize] + self.rank.rank(c, row);
+        }
+        output.reverse();
+        Some(output)
+    }
+
+    /// space of the index in bytes
+    pub fn size_in_bytes(&self) -> usize {
+        self.rank.size_in_bytes()
+            + self.c_array.len() * std::mem::size_of::<usize>()
+            + self.sampled.total_bytes()
+            + (self.sa_samples.len() + self.isa_samples.len()) * std::mem::size_of::<u32>()
+    }
+}
+
+#[cfg(test)]
+fn test_fmindex<R: ByteRank>() {
+    for mut text in crate::core::RandomStringGenerator::new(0..1024, 2) {
+        text.pop(); //@ remove the 0 byte
+        for sample_rate in [1, 3, 8] {
+            let index = FMIndex::<R>::new(&text, sample_rate).unwrap();
+            assert_eq!(index.extract(0, text.len()).unwrap(), text);
+            assert_eq!(index.extract(text.len(), 1), None);
+            for i in 0..text.len() {
+                assert_eq!(index.extract(i, 1).unwrap(), &text[i..i + 1]);
+            }
+            for i in 0..text.len() {
+                for j in i + 1..std::cmp::min(text.len(), i + 4) {
+                    let pattern = &text[i..j];
+                    let mut naive: Vec<usize> = (0..text.len() - pattern.len() + 1)
+                        .filter(|&k| &text[k..k + pattern.len()] == pattern)
+                        .collect();
+                    assert_eq!(index.count(pattern), naive.len());
+                    let mut located = index.locate(pattern);
+                    located.sort_unstable();
+                    naive.sort_unstable();
+                    assert_eq!(located, naive);
+                }
+            }
+        }
+    }
+    assert!(FMIndex::<R>::new(b"a\0b", 1).is_err());
+    assert!(FMIndex::<R>::new(b"ab", 0).is_err());
+}
+
+#[test]
+fn test_fmindex_sampled_occurrences() {
+    test_fmindex::<SampledOccurrences>();
+}
+
+#[test]
+fn test_fmindex_wavelet_tree() {
+    test_fmindex::<ByteWaveletTree>();
+}
+
+#[test]
+fn test_byte_rank() {
+    let text = crate::word::tribonacci_word(12);
+    let plain = SampledOccurrences::from_bwt(&text);
+    let wavelet = ByteWaveletTree::from_bwt(&text);
+    for (i, &c) in text.iter().enumerate() {
+        assert_eq!(plain.access(i), c);
+        assert_eq!(wavelet.access(i), c);
+    }
+    for c in [b'a', b'b', b'c', b'd', 0, 255] {
+        let mut count = 0;
+        for i in 0..=text.len() {
+            assert_eq!(plain.rank(c, i), count);
+            assert_eq!(wavelet.rank(c, i), count);
+            if i < text.len() && text[i] == c {
+                count += 1;
+            }
+        }
+    }
+}
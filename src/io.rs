@@ -1,95 +1,613 @@
-/// reads a file into a u8 vector
+use crate::error::{Error, Result};
+use std::convert::TryFrom;
+
+/// the compression format of an input, detected by its magic number
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Compression {
+    None,
+    Gzip,
+    Xz,
+    Zstd,
+}
+
+/// detects the compression format from the first bytes of an input
+pub fn detect_compression(header: &[u8]) -> Compression {
+    if header.starts_with(&[0x1f, 0x8b]) {
+        Compression::Gzip
+    } else if header.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+        Compression::Xz
+    } else if header.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        Compression::Zstd
+    } else {
+        Compression::None
+    }
+}
+
+/// wraps `reader` into a decoder if its first bytes are the magic number of a supported
+/// compression format (gzip, xz, or zstd), otherwise returns `reader` itself
+pub fn decompress<'a, R: std::io::BufRead + 'a>(
+    mut reader: R,
+) -> std::io::Result<Box<dyn std::io::Read + 'a>> {
+    let compression = detect_compression(reader.fill_buf()?);
+    Ok(match compression {
+        Compression::None => Box::new(reader),
+        Compression::Gzip => Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+        Compression::Xz => Box::new(xz2::bufread::XzDecoder::new_multi_decoder(reader)),
+        Compression::Zstd => Box::new(zstd::stream::read::Decoder::with_buffer(reader)?),
+    })
+}
+
+/// the compression format of a file
+fn file_compression(file: &mut std::fs::File) -> std::io::Result<Compression> {
+    use std::io::{Read, Seek};
+    let mut header = Vec::with_capacity(6);
+    file.by_ref().take(6).read_to_end(&mut header)?;
+    file.rewind()?;
+    Ok(detect_compression(&header))
+}
+
+/// reads the decompressed content of `reader`
+/// - `prefix_length` : the prefix in bytes to read. 0 means to read everything
+fn read_prefix<R: std::io::Read>(reader: R, prefix_length: usize) -> std::io::Result<Vec<u8>> {
+    use std::io::Read;
+    let limit = if prefix_length > 0 {
+        prefix_length as u64
+    } else {
+        u64::MAX
+    };
+    let mut buffer = Vec::new();
+    reader.take(limit).read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// opens `filename` for reading, and checks whether it is compressed
+fn open_file(filename: &str) -> Result<(std::fs::File, Compression)> {
+    let file_error = |source| Error::file(filename, source);
+    let mut file = std::fs::File::open(filename).map_err(file_error)?;
+    let compression = file_compression(&mut file).map_err(file_error)?;
+    Ok((file, compression))
+}
+
+/// reads the decompressed prefix of the compressed file `file` named `filename`
+fn read_compressed_file(
+    file: std::fs::File,
+    filename: &str,
+    prefix_length: usize,
+) -> Result<Vec<u8>> {
+    decompress(std::io::BufReader::new(file))
+        .and_then(|reader| read_prefix(reader, prefix_length))
+        .map_err(|source| Error::file(filename, source))
+}
+
+/// reads a file into a u8 vector, decompressing it if it is compressed
 /// - `prefix_length` : the prefix in bytes to read from `filename`. 0 means to read the entire file
-pub fn file2byte_vector(filename: &str, prefix_length: usize) -> Vec<u8> {
-    use std::fs;
+pub fn file2byte_vector(filename: &str, prefix_length: usize) -> Result<Vec<u8>> {
     use std::io::Read;
 
-    let path = std::path::Path::new(filename);
-    let mut f = fs::File::open(path).expect("no file found");
-    let metadata = fs::metadata(path).expect("unable to read metadata");
+    let (mut f, compression) = open_file(filename)?;
+    if compression != Compression::None {
+        return read_compressed_file(f, filename, prefix_length);
+    }
+    let file_error = |source| Error::file(filename, source);
+    let file_length = f.metadata().map_err(file_error)?.len();
     let buffer_length = if prefix_length > 0 {
-        std::cmp::min(prefix_length as u64, metadata.len())
+        std::cmp::min(prefix_length as u64, file_length)
     } else {
-        metadata.len()
+        file_length
     };
-    assert!(buffer_length <= usize::MAX as u64);
+    let buffer_length = usize::try_from(buffer_length).map_err(|_| {
+        Error::InvalidInput(format!("{} is too large for the address space", filename))
+    })?;
 
-    let mut buffer = vec![0; buffer_length as usize];
-    f.read_exact(&mut buffer).unwrap();
+    let mut buffer = vec![0; buffer_length];
+    f.read_exact(&mut buffer).map_err(file_error)?;
+    Ok(buffer)
+}
 
-    // let mut buffer = Vec::new();
-    // buffer.reserve_exact(buffer_length as usize);
-    // match f.read_to_end(&mut buffer) {
-    //     Ok(length) => assert_eq!(length, buffer.len()),
-    //     Err(x) => panic!("in file2byte_vector: {}", x),
-    // };
-    buffer
+/// reads stdin into a u8 vector, decompressing it if it is compressed
+/// - `prefix_length` : the prefix in bytes to read. 0 means to read everything
+pub fn stdin2byte_vector(prefix_length: usize) -> Result<Vec<u8>> {
+    Ok(read_prefix(
+        decompress(std::io::stdin().lock())?,
+        prefix_length,
+    )?)
 }
 
-pub fn stdin2byte_vector(prefix_length: usize) -> Vec<u8> {
-    use std::io::Read;
+pub fn file_or_stdin2byte_vector(filename: Option<&str>, prefix_length: usize) -> Result<Vec<u8>> {
+    match filename {
+        Some(filename) => file2byte_vector(filename, prefix_length),
+        None => stdin2byte_vector(prefix_length),
+    }
+}
+
+/// the bytes of an input, either memory-mapped from a file or read into memory from stdin
+pub enum InputBytes {
+    /// a read-only mapping of a file, of which only the first `length` bytes are used
+    Mapped {
+        map: memmap2::Mmap,
+        length: usize,
+    },
+    Buffered(Vec<u8>),
+}
 
-    // let stdin = std::io::stdin();
-    let mut reader = std::io::stdin();
-    // let reader = stdin.lock();
-    if prefix_length > 0 {
-        let mut buffer = vec![0; prefix_length];
-        reader.read_exact(buffer.as_mut_slice()).unwrap();
-        buffer
+impl InputBytes {
+    pub fn as_slice(&self) -> &[u8] {
+        self
+    }
+}
+
+impl std::fmt::Debug for InputBytes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
+impl std::ops::Deref for InputBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            InputBytes::Mapped { map, length } => &map[..*length],
+            InputBytes::Buffered(buffer) => buffer,
+        }
+    }
+}
+
+/// maps a file read-only into memory, or decodes it into memory if it is compressed
+/// - `prefix_length` : the prefix in bytes to use from `filename`. 0 means to use the entire file
+pub fn file2mapped_bytes(filename: &str, prefix_length: usize) -> Result<InputBytes> {
+    let (file, compression) = open_file(filename)?;
+    if compression != Compression::None {
+        //@ compressed files cannot be mapped, but need to be decoded into memory
+        return Ok(InputBytes::Buffered(read_compressed_file(
+            file,
+            filename,
+            prefix_length,
+        )?));
+    }
+    let file_error = |source| Error::file(filename, source);
+    let file_length = file.metadata().map_err(file_error)?.len();
+    let file_length = usize::try_from(file_length).map_err(|_| {
+        Error::InvalidInput(format!("{} is too large for the address space", filename))
+    })?;
+    let length = if prefix_length > 0 {
+        std::cmp::min(prefix_length, file_length)
     } else {
-        let mut buffer = Vec::new();
-        reader.read_to_end(&mut buffer).unwrap();
-        buffer
+        file_length
+    };
+    if length == 0 {
+        //@ empty mappings are not supported on all platforms
+        return Ok(InputBytes::Buffered(Vec::new()));
     }
+    //@ the mapping is only valid as long as no other process truncates or modifies the file
+    let map = unsafe { memmap2::Mmap::map(&file) }.map_err(file_error)?;
+    Ok(InputBytes::Mapped { map, length })
 }
 
-pub fn file_or_stdin2byte_vector(filename: Option<&str>, prefix_length: usize) -> Vec<u8> {
+/// maps a file into memory, or reads stdin into a buffer if no filename is given
+pub fn file_or_stdin2mapped_bytes(
+    filename: Option<&str>,
+    prefix_length: usize,
+) -> Result<InputBytes> {
     match filename {
-        Some(filename) => file2byte_vector(filename, prefix_length),
-        None => stdin2byte_vector(prefix_length),
+        Some(filename) => file2mapped_bytes(filename, prefix_length),
+        None => Ok(InputBytes::Buffered(stdin2byte_vector(prefix_length)?)),
+    }
+}
+
+/// decodes a byte vector into integer symbols, each stored in `symbol_width` bytes in
+/// little-endian order. `symbol_width` must be 1, 2, 4 or 8.
+pub fn byte_vector2symbols(bytes: &[u8], symbol_width: usize) -> Result<Vec<u64>> {
+    if ![1, 2, 4, 8].contains(&symbol_width) {
+        return Err(Error::InvalidArgument(format!(
+            "the symbol width must be 1, 2, 4 or 8 bytes, but is {}",
+            symbol_width
+        )));
+    }
+    if !bytes.len().is_multiple_of(symbol_width) {
+        return Err(Error::InvalidInput(format!(
+            "the input length {} is not a multiple of the symbol width {}",
+            bytes.len(),
+            symbol_width
+        )));
     }
+    Ok(bytes
+        .chunks_exact(symbol_width)
+        .map(|chunk| {
+            let mut buffer = [0u8; 8];
+            buffer[..symbol_width].copy_from_slice(chunk);
+            u64::from_le_bytes(buffer)
+        })
+        .collect())
+}
+
+/// reads a file or stdin into a vector of integer symbols of `symbol_width` bytes each
+/// - `prefix_length` : the prefix in symbols to read. 0 means to read the entire input
+pub fn file_or_stdin2symbol_vector(
+    filename: Option<&str>,
+    prefix_length: usize,
+    symbol_width: usize,
+) -> Result<Vec<u64>> {
+    let bytes = file_or_stdin2mapped_bytes(filename, prefix_length * symbol_width)?;
+    byte_vector2symbols(&bytes, symbol_width)
 }
 
-/// open an input file or use stdin if no filename is given
-pub fn stream_or_stdin(filename: Option<&str>) -> Box<dyn std::io::Read> {
+/// writes integer symbols in little-endian order with `symbol_width` bytes each
+pub fn write_symbols<W: std::io::Write, C: Copy + Into<u64>>(
+    writer: &mut W,
+    symbols: &[C],
+    symbol_width: usize,
+) -> std::io::Result<()> {
+    debug_assert!([1, 2, 4, 8].contains(&symbol_width));
+    for &c in symbols {
+        writer.write_all(&c.into().to_le_bytes()[..symbol_width])?;
+    }
+    Ok(())
+}
+
+/// open an input file or use stdin if no filename is given, decompressing the input if it
+/// is compressed with gzip, xz, or zstd
+pub fn stream_or_stdin(filename: Option<&str>) -> Result<Box<dyn std::io::Read>> {
     match filename {
         Some(filename) => {
             // info!("filename: {}", filename);
-            let path = std::path::Path::new(filename);
-            Box::new(std::io::BufReader::new(std::fs::File::open(path).unwrap()))
-                as Box<dyn std::io::Read>
+            let (file, _) = open_file(filename)?;
+            decompress(std::io::BufReader::new(file))
+                .map_err(|source| Error::file(filename, source))
         }
-        None => Box::new(std::io::stdin()) as Box<dyn std::io::Read>,
+        None => Ok(decompress(std::io::stdin().lock())?),
+    }
+}
+
+/// open a file or stdin like `stream_or_stdin`, but read only its first `prefixlength` bytes
+/// (0 for the entire input)
+pub fn prefix_stream_or_stdin(
+    filename: Option<&str>,
+    prefixlength: usize,
+) -> Result<Box<dyn std::io::Read>> {
+    let reader = stream_or_stdin(filename)?;
+    if prefixlength == 0 {
+        return Ok(reader);
     }
+    Ok(Box::new(std::io::Read::take(reader, prefixlength as u64)))
 }
 
-/// open an file for output or use stdout if no filename is given
-pub fn stream_or_stdout(filename: Option<&str>) -> Box<dyn std::io::Write> {
+/// open an file for output or use stdout if no filename is given.
+/// The output is buffered and flushed when the writer is dropped;
+/// call `flush` explicitly to observe write errors.
+pub fn stream_or_stdout(filename: Option<&str>) -> Result<Box<dyn std::io::Write>> {
     match filename {
         Some(filename) => {
             // info!("filename: {}", filename);
-            let path = std::path::Path::new(filename);
-            Box::new(std::fs::File::create(path).unwrap()) as Box<dyn std::io::Write>
+            let file =
+                std::fs::File::create(filename).map_err(|source| Error::file(filename, source))?;
+            Ok(Box::new(std::io::BufWriter::new(file)) as Box<dyn std::io::Write>)
+        }
+        None => {
+            Ok(Box::new(std::io::BufWriter::new(std::io::stdout().lock()))
+                as Box<dyn std::io::Write>)
         }
-        None => Box::new(std::io::stdout()) as Box<dyn std::io::Write>,
     }
 }
 
-/// read a single u8 character
+/// read a single u8 character.
+/// Returns an error of kind `UnexpectedEof` at the end of the input.
+/// This performs one `read` call per byte; prefer `ByteReader` for streaming over large inputs.
 pub fn read_char<R: std::io::Read>(reader: &mut R) -> std::io::Result<u8> {
     let mut buffer = [0u8];
-    match reader.read(buffer.as_mut()) {
-        Result::Ok(u) => {
-            if u == 1 {
-                Ok(buffer[0])
-            } else {
-                Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    "end of file",
-                ))
+    reader.read_exact(buffer.as_mut())?;
+    Ok(buffer[0])
+}
+
+/// the default buffer size of `ByteReader`
+pub const BYTE_READER_BUFFER_SIZE: usize = 1 << 16;
+
+/// a reader counting the bytes read from the reader it wraps
+pub struct CountingReader<R: std::io::Read> {
+    reader: R,
+    count: usize,
+}
+
+impl<R: std::io::Read> CountingReader<R> {
+    pub fn new(reader: R) -> CountingReader<R> {
+        CountingReader { reader, count: 0 }
+    }
+
+    /// the number of bytes read so far
+    pub fn count(&self) -> usize {
+        self.count
+    }
+}
+
+impl<R: std::io::Read> std::io::Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let length = self.reader.read(buf)?;
+        self.count += length;
+        Ok(length)
+    }
+}
+
+/// A buffered iterator over the bytes of a reader.
+/// It yields `Ok(byte)` for each byte and `None` at the end of the input, while read errors
+/// are yielded as `Some(Err(error))`. Interrupted reads are retried.
+pub struct ByteReader<R: std::io::Read> {
+    reader: R,
+    buffer: Box<[u8]>,
+    /// the position of the next byte to return in `buffer`
+    position: usize,
+    /// the number of valid bytes in `buffer`
+    filled: usize,
+}
+
+impl<R: std::io::Read> ByteReader<R> {
+    pub fn new(reader: R) -> ByteReader<R> {
+        ByteReader::with_capacity(BYTE_READER_BUFFER_SIZE, reader)
+    }
+
+    pub fn with_capacity(capacity: usize, reader: R) -> ByteReader<R> {
+        assert!(capacity > 0);
+        ByteReader {
+            reader,
+            buffer: vec![0; capacity].into_boxed_slice(),
+            position: 0,
+            filled: 0,
+        }
+    }
+
+    /// the next byte, or `None` at the end of the input
+    pub fn next_byte(&mut self) -> std::io::Result<Option<u8>> {
+        if self.position == self.filled {
+            loop {
+                match self.reader.read(&mut self.buffer) {
+                    Ok(0) => return Ok(None),
+                    Ok(length) => {
+                        self.position = 0;
+                        self.filled = length;
+                        break;
+                    }
+                    Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                    Err(error) => return Err(error),
+                }
+            }
+        }
+        let c = self.buffer[self.position];
+        self.position += 1;
+        Ok(Some(c))
+    }
+}
+
+impl<R: std::io::Read> Iterator for ByteReader<R> {
+    type Item = std::io::Result<u8>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_byte().transpose()
+    }
+}
+
+/// the format of an input file
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum InputFormat {
+    /// the input is taken as a single text as it is
+    Raw,
+    /// records starting with a header line `>name`, followed by sequence lines
+    Fasta,
+    /// records of four lines `@name`, sequence, `+`, and qualities
+    Fastq,
+    /// each non-empty line is a record
+    Lines,
+}
+
+/// how the records of a collection are processed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum CollectionMode {
+    /// concatenate all records, optionally delimited by a separator byte
+    Concatenate,
+    /// process each record separately, reporting a result record per record
+    PerRecord,
+    /// process the concatenation of all records, each terminated by its own sentinel symbol
+    Generalized,
+}
+
+/// a named sequence of a collection
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    pub name: String,
+    pub sequence: Vec<u8>,
+}
+
+/// the lines of `bytes` without their line endings (\n or \r\n)
+pub fn lines(bytes: &[u8]) -> impl Iterator<Item = &[u8]> {
+    bytes
+        .split(|&c| c == b'\n')
+        .map(|line| line.strip_suffix(b"\r").unwrap_or(line))
+}
+
+/// the record name of a header line, i.e., the text up to the first whitespace
+fn header_name(header: &[u8]) -> String {
+    let end = header
+        .iter()
+        .position(|c| c.is_ascii_whitespace())
+        .unwrap_or(header.len());
+    String::from_utf8_lossy(&header[..end]).into_owned()
+}
+
+/// parses FASTA records, skipping comment lines starting with ';'
+pub fn parse_fasta(bytes: &[u8]) -> Result<Vec<Record>> {
+    let mut records: Vec<Record> = Vec::new();
+    for line in lines(bytes) {
+        if let Some(header) = line.strip_prefix(b">") {
+            records.push(Record {
+                name: header_name(header),
+                sequence: Vec::new(),
+            });
+        } else if !line.starts_with(b";") {
+            match records.last_mut() {
+                Some(record) => record.sequence.extend_from_slice(line),
+                None if line.is_empty() => {}
+                None => {
+                    return Err(invalid_record(
+                        "FASTA input has to start with a header line",
+                    ))
+                }
+            }
+        }
+    }
+    Ok(records)
+}
+
+fn invalid_record(message: &str) -> Error {
+    Error::InvalidInput(message.to_string())
+}
+
+/// parses FASTQ records consisting of exactly four lines each
+pub fn parse_fastq(bytes: &[u8]) -> Result<Vec<Record>> {
+    let mut records = Vec::new();
+    let mut iter = lines(bytes).filter(|line| !line.is_empty());
+    while let Some(header) = iter.next() {
+        let header = header
+            .strip_prefix(b"@")
+            .ok_or_else(|| invalid_record("a FASTQ record has to start with '@'"))?;
+        let sequence = iter
+            .next()
+            .ok_or_else(|| invalid_record("FASTQ record without sequence"))?;
+        let separator = iter
+            .next()
+            .ok_or_else(|| invalid_record("FASTQ record without '+' line"))?;
+        if !separator.starts_with(b"+") {
+            return Err(invalid_record(
+                "the third line of a FASTQ record has to start with '+'",
+            ));
+        }
+        let qualities = iter
+            .next()
+            .ok_or_else(|| invalid_record("FASTQ record without qualities"))?;
+        if qualities.len() != sequence.len() {
+            return Err(invalid_record(
+                "the qualities of a FASTQ record have to be as long as its sequence",
+            ));
+        }
+        records.push(Record {
+            name: header_name(header),
+            sequence: sequence.to_vec(),
+        });
+    }
+    Ok(records)
+}
+
+/// parses each non-empty line as a record, named by its line number starting at 1
+pub fn parse_lines(bytes: &[u8]) -> Vec<Record> {
+    lines(bytes)
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(number, line)| Record {
+            name: (number + 1).to_string(),
+            sequence: line.to_vec(),
+        })
+        .collect()
+}
+
+/// parses `bytes` in the given format. A raw input gives a single record named "raw"
+pub fn parse_records(bytes: &[u8], format: InputFormat) -> Result<Vec<Record>> {
+    match format {
+        InputFormat::Raw => Ok(vec![Record {
+            name: String::from("raw"),
+            sequence: bytes.to_vec(),
+        }]),
+        InputFormat::Fasta => parse_fasta(bytes),
+        InputFormat::Fastq => parse_fastq(bytes),
+        InputFormat::Lines => Ok(parse_lines(bytes)),
+    }
+}
+
+/// folds a DNA sequence to upper case and drops all symbols other than A, C, G, and T
+pub fn fold_dna(sequence: &mut Vec<u8>) {
+    sequence.retain_mut(|c| {
+        *c = c.to_ascii_uppercase();
+        matches!(*c, b'A' | b'C' | b'G' | b'T')
+    });
+}
+
+/// concatenates the sequences of `records`, putting `separator` (if any) after each record
+pub fn concatenate_records(records: &[Record], separator: Option<u8>) -> Vec<u8> {
+    let mut text = Vec::with_capacity(records.iter().map(|r| r.sequence.len() + 1).sum());
+    for record in records {
+        text.extend_from_slice(&record.sequence);
+        if let Some(separator) = separator {
+            text.push(separator);
+        }
+    }
+    text
+}
+
+/// builds the integer text S_0 $_0 S_1 $_1 ... of the sequences S_i of `records`,
+/// where the sentinel $_i is the symbol i and each byte c is mapped to c + records.len(),
+/// such that all sentinels are distinct and smaller than all other symbols
+pub fn generalized_text(records: &[Record]) -> Vec<u64> {
+    let offset = records.len() as u64;
+    let mut text = Vec::with_capacity(records.iter().map(|r| r.sequence.len() + 1).sum());
+    for (i, record) in records.iter().enumerate() {
+        text.extend(record.sequence.iter().map(|&c| c as u64 + offset));
+        text.push(i as u64);
+    }
+    text
+}
+
+/// Parses a separator byte given as a single ASCII character, as one of the escapes `\n`, `\r`,
+/// `\t` and `\0`, or as a decimal byte value.
+pub fn parse_separator(separator: &str) -> std::result::Result<u8, String> {
+    match separator {
+        "\\n" => Ok(b'\n'),
+        "\\r" => Ok(b'\r'),
+        "\\t" => Ok(b'\t'),
+        "\\0" => Ok(0),
+        _ if separator.len() == 1 => Ok(separator.as_bytes()[0]),
+        _ => separator.parse::<u8>().map_err(|_| {
+            format!(
+                "'{}' is neither a single character, an escape like \\n, nor a byte value",
+                separator
+            )
+        }),
+    }
+}
+
+/// command line options for reading collections of sequences
+#[derive(clap::Args, Clone, Debug)]
+pub struct CollectionArgs {
+    /// the format of the input
+    #[arg(long, value_enum, default_value_t = InputFormat::Raw)]
+    pub input_format: InputFormat,
+
+    /// how to process the records of a collection
+    #[arg(long, value_enum, default_value_t = CollectionMode::Concatenate)]
+    pub collection: CollectionMode,
+
+    /// the byte to put after each record when concatenating records: a character like `$`, an
+    /// escape like `\n`, or a byte value like `36`
+    #[arg(long, value_parser = parse_separator)]
+    pub separator: Option<u8>,
+
+    /// fold DNA sequences to upper case and drop all symbols other than A, C, G, and T
+    #[arg(long)]
+    pub dna: bool,
+}
+
+impl CollectionArgs {
+    /// whether the input is read as a single text without any processing
+    pub fn is_plain(&self) -> bool {
+        self.input_format == InputFormat::Raw
+            && self.collection == CollectionMode::Concatenate
+            && self.separator.is_none()
+            && !self.dna
+    }
+
+    /// parses the records of `bytes`, applying the DNA folding if requested
+    pub fn records(&self, bytes: &[u8]) -> Result<Vec<Record>> {
+        let mut records = parse_records(bytes, self.input_format)?;
+        if self.dna {
+            for record in records.iter_mut() {
+                fold_dna(&mut record.sequence);
             }
-            // assert_eq!(u, 1);
         }
-        Err(error) => Err(error),
+        Ok(records)
     }
 }
@@ -1,5 +1,19 @@
+pub mod batch;
 #[allow(dead_code)]
 pub mod core;
+pub mod error;
+pub mod fmindex;
 #[allow(dead_code)]
 pub mod io;
+pub mod measure;
+pub mod necklace;
+pub mod random;
+pub mod report;
+pub mod rindex;
+pub mod rmq;
+pub mod search;
+pub mod sensitivity;
+pub mod suffixautomaton;
+pub mod tools;
+pub mod waveletmatrix;
 pub mod word;
@@ -0,0 +1,239 @@
+//! Compressibility measures of strings, evaluated directly on a byte string, e.g., for searching
+//! strings that are extremal under a measure, or for the change of a measure under edits.
+use crate::core;
+use crate::error::{Error, Result};
+use crate::tools::{count_lexparse, count_z};
+use std::collections::HashMap;
+
+/// the length up to which LZ77 is computed naively, since building the suffix array and the range
+/// minimum query structure does not pay off for shorter strings
+const NAIVE_LZ77_LENGTH: usize = 64;
+
+/// a measure of a string
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Measure {
+    /// the number of runs of the BWT of the string with an appended 0 byte as the dollar sign
+    R,
+    /// the number of factors of the (self-referential) LZ77 factorization
+    Z,
+    /// the number of factors of the lex-parse
+    Lexparse,
+    /// the number of runs of the bijective BWT
+    BbwtRuns,
+    /// the number of factors of the Lyndon factorization
+    LyndonFactors,
+    /// the size of a smallest string attractor, computed exhaustively for strings of at most 64 letters
+    Gamma,
+}
+
+impl Measure {
+    /// the name of the measure as given on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            Measure::R => "r",
+            Measure::Z => "z",
+            Measure::Lexparse => "lexparse",
+            Measure::BbwtRuns => "bbwt-runs",
+            Measure::LyndonFactors => "lyndon-factors",
+            Measure::Gamma => "gamma",
+        }
+    }
+
+    /// whether the measure does not change when renaming the letters of the string
+    pub fn is_renaming_invariant(&self) -> bool {
+        matches!(self, Measure::Z | Measure::Gamma)
+    }
+
+    /// The value of the measure for `text`, which must not contain the byte 0 for the measure r.
+    pub fn evaluate(&self, text: &[u8]) -> Result<usize> {
+        if text.is_empty() {
+            return Ok(0);
+        }
+        Ok(match self {
+            Measure::R => {
+                let mut text = text.to_vec();
+                text.push(0);
+                core::number_of_runs_in(&core::bwt_from_text_by_sa(&text)?)
+            }
+            Measure::Z if text.len() <= NAIVE_LZ77_LENGTH => lz77_factors(text),
+            Measure::Z => {
+                let sa = count_z::byte_suffix_array(text);
+                count_z::compute_factors(text, &sa, &mut std::time::Instant::now()).len()
+            }
+            Measure::Lexparse => lexparse_factors(text)?,
+            Measure::BbwtRuns => core::number_of_runs_in(&core::bbwt_naive(text)),
+            Measure::LyndonFactors => core::duval(text).len(),
+            Measure::Gamma => smallest_attractor_size(text)?,
+        })
+    }
+}
+
+/// the number of LZ77 factors of `text`, where a factor is the longest prefix of the remaining
+/// suffix that starts earlier (possibly overlapping), or a single letter. Takes quadratic time per
+/// factor, which is fast for the short strings of exhaustive searches.
+fn lz77_factors(text: &[u8]) -> usize {
+    let mut factors = 0;
+    let mut i = 0;
+    while i < text.len() {
+        let longest = (0..i)
+            .map(|j| {
+                text[i..]
+                    .iter()
+                    .zip(&text[j..])
+                    .take_while(|(a, b)| a == b)
+                    .count()
+            })
+            .max()
+            .unwrap_or(0);
+        i += longest.max(1);
+        factors += 1;
+    }
+    factors
+}
+
+/// the number of factors of the lex-parse of `text`, where the factor starting at position i is
+/// the longest common prefix of the suffix starting at i and its lexicographic predecessor, or a
+/// single letter
+fn lexparse_factors(text: &[u8]) -> Result<usize> {
+    let n = text.len();
+    let mut text = text.to_vec();
+    text.push(0); //@ the dollar sign is the lexicographic predecessor of the smallest suffix
+    let sa = core::suffixarray_prefix_doubling(&text)?;
+    let phi = core::compute_phi(&sa);
+    let plcp = core::compute_plcp(&text, &phi);
+    Ok(count_lexparse::compute_lexparse(&text[..n], &plcp, &phi).len())
+}
+
+/// Whether a set of positions with at most `budget` further positions hits each of the sets of
+/// positions `constraints` not hit by `chosen`, given as bit masks. Branches on the positions of
+/// the smallest set that is not hit yet.
+fn can_hit(constraints: &[u64], chosen: u64, budget: usize) -> bool {
+    match constraints
+        .iter()
+        .filter(|&&mask| mask & chosen == 0)
+        .min_by_key(|mask| mask.count_ones())
+    {
+        None => true,
+        Some(_) if budget == 0 => false,
+        Some(&mask) => (0..64)
+            .filter(|p| mask & (1 << p) != 0)
+            .any(|p| can_hit(constraints, chosen | (1 << p), budget - 1)),
+    }
+}
+
+/// The size of a smallest string attractor of `text`, i.e., of a smallest set of positions such
+/// that each distinct substring has an occurrence containing one of them. Computes the positions
+/// covered by the occurrences of each distinct substring, and searches for a smallest set hitting
+/// all of them, which takes exponential time and is restricted to strings of at most 64 letters.
+fn smallest_attractor_size(text: &[u8]) -> Result<usize> {
+    let n = text.len();
+    if n > 64 {
+        return Err(Error::InvalidInput(format!(
+            "gamma is computed only for strings of at most 64 letters, but the string has {}",
+            n
+        )));
+    }
+    let mut masks = Vec::new();
+    for length in 1..=n {
+        let mut occurrences = HashMap::<&[u8], u64>::new();
+        for i in 0..=n - length {
+            *occurrences.entry(&text[i..i + length]).or_default() |=
+                (u64::MAX >> (64 - length)) << i;
+        }
+        masks.extend(occurrences.into_values());
+    }
+    masks.sort_unstable();
+    masks.dedup();
+    //@ hitting a subset of a mask hits the mask
+    let constraints: Vec<u64> = masks
+        .iter()
+        .copied()
+        .filter(|&mask| {
+            !masks
+                .iter()
+                .any(|&other| other != mask && other & mask == other)
+        })
+        .collect();
+    Ok((0..=n)
+        .find(|&budget| can_hit(&constraints, 0, budget))
+        .unwrap())
+}
+
+#[test]
+fn test_measures() {
+    //@ ab aabab aab aab
+    assert_eq!(
+        Measure::LyndonFactors.evaluate(b"abaababaabaab").unwrap(),
+        4
+    );
+    assert_eq!(Measure::Z.evaluate(b"abababab").unwrap(), 3);
+    assert_eq!(Measure::Z.evaluate(b"aaaa").unwrap(), 2);
+    assert_eq!(Measure::Z.evaluate(b"abcabc").unwrap(), 4);
+    assert_eq!(Measure::Lexparse.evaluate(b"banana").unwrap(), 4);
+    assert_eq!(Measure::R.evaluate(b"banana").unwrap(), 5); //@ annb$aa
+    assert_eq!(Measure::BbwtRuns.evaluate(b"banana").unwrap(), 4);
+    for measure in [
+        Measure::R,
+        Measure::Z,
+        Measure::Lexparse,
+        Measure::BbwtRuns,
+        Measure::LyndonFactors,
+        Measure::Gamma,
+    ] {
+        assert_eq!(measure.evaluate(b"").unwrap(), 0);
+        //@ the BWT a$ of a has two runs
+        let expected = if measure == Measure::R { 2 } else { 1 };
+        assert_eq!(measure.evaluate(b"a").unwrap(), expected);
+    }
+    //@ z is invariant under renaming, but r is not
+    assert_eq!(
+        Measure::Z.evaluate(b"aab").unwrap(),
+        Measure::Z.evaluate(b"bba").unwrap()
+    );
+    assert_eq!(Measure::R.evaluate(b"aba").unwrap(), 4); //@ ab$a
+    assert_eq!(Measure::R.evaluate(b"bab").unwrap(), 3); //@ bba$
+
+    //@ z is computed by the suffix array for longer strings
+    let text = crate::word::fibonacci_word(12);
+    assert!(text.len() > NAIVE_LZ77_LENGTH);
+    assert_eq!(Measure::Z.evaluate(&text).unwrap(), lz77_factors(&text));
+}
+
+#[test]
+fn test_gamma() {
+    assert_eq!(Measure::Gamma.evaluate(b"aaaa").unwrap(), 1);
+    assert_eq!(Measure::Gamma.evaluate(b"abab").unwrap(), 2);
+    assert_eq!(Measure::Gamma.evaluate(b"abcabc").unwrap(), 3);
+    //@ the Fibonacci words have attractors of size 2
+    assert_eq!(
+        Measure::Gamma
+            .evaluate(&crate::word::fibonacci_word(8))
+            .unwrap(),
+        2
+    );
+    assert!(Measure::Gamma.evaluate(&[b'a'; 65]).is_err());
+
+    //@ compare with checking all sets of positions
+    let is_attractor = |text: &[u8], positions: &[usize]| {
+        (0..text.len()).all(|i| {
+            (i + 1..=text.len()).all(|j| {
+                let factor = &text[i..j];
+                (0..=text.len() - factor.len()).any(|k| {
+                    &text[k..k + factor.len()] == factor
+                        && positions
+                            .iter()
+                            .any(|&p| (k..k + factor.len()).contains(&p))
+                })
+            })
+        })
+    };
+    for text in itertools::Itertools::multi_cartesian_product((0..7).map(|_| b'a'..=b'c')) {
+        let gamma = (0..=text.len())
+            .find(|&size| {
+                itertools::Itertools::combinations(0..text.len(), size)
+                    .any(|positions| is_attractor(&text, &positions))
+            })
+            .unwrap();
+        assert_eq!(Measure::Gamma.evaluate(&text).unwrap(), gamma);
+    }
+}
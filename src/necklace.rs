@@ -0,0 +1,505 @@
+//! Necklaces, prenecklaces, bracelets and Lyndon words of a fixed length over the alphabet
+//! {0, ..., sigma-1}: their enumeration in lexicographic order, their numbers, and the ranking and
+//! unranking of necklaces and Lyndon words.
+use crate::core;
+use crate::error::{Error, Result};
+use std::convert::TryFrom;
+
+/// the kind of words enumerated by [`NecklaceGenerator`]
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NecklaceKind {
+    Prenecklace,
+    Necklace,
+    Lyndon,
+    Bracelet,
+}
+
+/// Enumerates the prenecklaces, necklaces, bracelets or Lyndon words of length n in
+/// lexicographic order, with the FKM algorithm of Fredricksen, Kessler and Maiorana that visits all
+/// prenecklaces of length n, each with the length p of its longest Lyndon prefix. A prenecklace is
+/// a necklace if p divides n, and a Lyndon word if p = n.
+/// Frank Ruskey, Carla Savage, Terry Min Yih Wang: Generating necklaces. J. Algorithms 13(3): 414-430 (1992)
+pub struct NecklaceGenerator {
+    kind: NecklaceKind,
+    sigma: usize,
+    word: Vec<u8>,
+    period: usize, //@ the length of the longest Lyndon prefix of `word`, or 0 if the enumeration ended
+    pending_empty: bool, //@ whether the empty word, which has no Lyndon prefix, is still to be yielded
+}
+
+impl NecklaceGenerator {
+    /// the generator of the words of `kind` of the given length over sigma letters, which must fit
+    /// into bytes
+    pub fn new(length: usize, sigma: usize, kind: NecklaceKind) -> Result<NecklaceGenerator> {
+        check_sigma(sigma)?;
+        Ok(NecklaceGenerator {
+            kind,
+            sigma,
+            word: vec![0; length],
+            period: if length == 0 || sigma == 0 { 0 } else { 1 },
+            //@ the empty word is a necklace, but not a Lyndon word
+            pending_empty: length == 0 && kind != NecklaceKind::Lyndon,
+        })
+    }
+
+    /// advances to the next prenecklace, and returns whether there is one
+    fn advance(&mut self) -> bool {
+        let n = self.word.len();
+        let z = (self.sigma - 1) as u8;
+        let Some(i) = self.word.iter().rposition(|&c| c < z) else {
+            return false;
+        };
+        self.word[i] += 1;
+        for j in i + 1..n {
+            self.word[j] = self.word[j - (i + 1)];
+        }
+        self.period = i + 1;
+        true
+    }
+
+    fn accepts(&self) -> bool {
+        let n = self.word.len();
+        match self.kind {
+            NecklaceKind::Prenecklace => true,
+            NecklaceKind::Necklace => n.is_multiple_of(self.period),
+            NecklaceKind::Lyndon => self.period == n,
+            NecklaceKind::Bracelet => n.is_multiple_of(self.period) && is_bracelet(&self.word),
+        }
+    }
+}
+
+impl Iterator for NecklaceGenerator {
+    type Item = Vec<u8>;
+
+    fn next(&mut self) -> Option<Vec<u8>> {
+        if std::mem::take(&mut self.pending_empty) {
+            return Some(Vec::new());
+        }
+        while self.period > 0 {
+            let found = self.accepts().then(|| self.word.clone());
+            if !self.advance() {
+                self.period = 0;
+            }
+            if found.is_some() {
+                return found;
+            }
+        }
+        None
+    }
+}
+
+/// the lexicographically smallest rotation of `word`, i.e., the necklace of its conjugacy class
+pub fn least_rotation(word: &[u8]) -> Vec<u8> {
+    let mut rotation = word.to_vec();
+    if !word.is_empty() {
+        rotation.rotate_left(core::lyndon_conjugate(word));
+    }
+    rotation
+}
+
+/// whether the necklace is a bracelet, i.e., not larger than the necklace of its reversal
+pub fn is_bracelet(necklace: &[u8]) -> bool {
+    let mut reversal = necklace.to_vec();
+    reversal.reverse();
+    necklace <= least_rotation(&reversal).as_slice()
+}
+
+/// the divisors of n in increasing order
+fn divisors(n: usize) -> Vec<usize> {
+    (1..=n).filter(|d| n.is_multiple_of(*d)).collect()
+}
+
+/// the Möbius function
+fn moebius(mut n: usize) -> i128 {
+    let mut result = 1;
+    let mut p = 2;
+    while p * p <= n {
+        if n.is_multiple_of(p) {
+            n /= p;
+            if n.is_multiple_of(p) {
+                return 0;
+            }
+            result = -result;
+        }
+        p += 1;
+    }
+    if n > 1 {
+        result = -result;
+    }
+    result
+}
+
+/// Euler's totient function
+fn totient(n: usize) -> usize {
+    (1..=n).filter(|&k| num::integer::gcd(k, n) == 1).count()
+}
+
+/// sigma^exponent, or an error if it does not fit into 128 bits with room for sums of up to
+/// `exponent` such powers
+fn checked_power(sigma: usize, exponent: usize) -> Result<i128> {
+    u32::try_from(exponent)
+        .ok()
+        .and_then(|exponent| (sigma as i128).checked_pow(exponent))
+        .filter(|power| power.checked_mul(exponent.max(1) as i128).is_some())
+        .ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "the number of words of length {} over {} letters does not fit into 128 bits",
+                exponent, sigma
+            ))
+        })
+}
+
+/// Moreau's necklace polynomial M(sigma, n) = 1/n sum_{d | n} mu(d) sigma^{n/d}, the number of
+/// Lyndon words of length n over sigma letters
+pub fn lyndon_count(sigma: usize, n: usize) -> Result<u128> {
+    if n == 0 {
+        return Ok(0);
+    }
+    let mut sum = 0;
+    for d in divisors(n) {
+        sum += moebius(d) * checked_power(sigma, n / d)?;
+    }
+    Ok((sum / n as i128) as u128)
+}
+
+/// the number 1/n sum_{d | n} phi(d) sigma^{n/d} of necklaces of length n over sigma letters
+pub fn necklace_count(sigma: usize, n: usize) -> Result<u128> {
+    if n == 0 {
+        return Ok(1);
+    }
+    let mut sum = 0;
+    for d in divisors(n) {
+        sum += totient(d) as i128 * checked_power(sigma, n / d)?;
+    }
+    Ok((sum / n as i128) as u128)
+}
+
+/// the number of prenecklaces of length n over sigma letters, which is the sum of the numbers of
+/// Lyndon words of length 1 to n, since each prenecklace is a prefix of a power of a unique Lyndon
+/// word not longer than n
+pub fn prenecklace_count(sigma: usize, n: usize) -> Result<u128> {
+    if n == 0 {
+        return Ok(1);
+    }
+    let mut sum = 0;
+    for length in 1..=n {
+        sum += lyndon_count(sigma, length)?;
+    }
+    Ok(sum)
+}
+
+/// the number of bracelets of length n over sigma letters, i.e., necklaces up to reversal
+pub fn bracelet_count(sigma: usize, n: usize) -> Result<u128> {
+    if n == 0 {
+        return Ok(1);
+    }
+    let necklaces = necklace_count(sigma, n)? as i128;
+    let palindromic = if n.is_multiple_of(2) {
+        (sigma as i128 + 1) * checked_power(sigma, n / 2)? / 2
+    } else {
+        checked_power(sigma, n.div_ceil(2))?
+    };
+    Ok(((necklaces + palindromic) / 2) as u128)
+}
+
+/// checks that the letters {0, ..., sigma-1} fit into bytes
+fn check_sigma(sigma: usize) -> Result<()> {
+    if sigma > 256 {
+        return Err(Error::InvalidArgument(format!(
+            "the alphabet size {} exceeds the 256 byte values",
+            sigma
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that `word` consists of letters smaller than sigma, and returns the length of its longest
+/// Lyndon prefix if it is a prenecklace.
+fn prenecklace_period(word: &[u8], sigma: usize) -> Result<Option<usize>> {
+    if let Some(&c) = word.iter().find(|&&c| c as usize >= sigma) {
+        return Err(Error::InvalidArgument(format!(
+            "the letter {} is not in the alphabet {{0, ..., {}}}",
+            c,
+            sigma.saturating_sub(1)
+        )));
+    }
+    let mut period = 1;
+    for i in 1..word.len() {
+        if word[i] < word[i - period] {
+            return Ok(None);
+        }
+        if word[i] > word[i - period] {
+            period = i + 1;
+        }
+    }
+    Ok(Some(period))
+}
+
+/// For each length d dividing the length n of the prenecklace `alpha`, the number A_d of Lyndon
+/// words x of length d with x^{n/d} <= alpha.
+/// Counts the strings s of length d whose rotations r all satisfy r^{n/d} > alpha. These are the
+/// cyclic concatenations of the blocks alpha[..j]c with c > alpha[j], which form a prefix code.
+/// Then sigma^d minus this number is sum_{p | d} p A_p, which gives A_d by Möbius inversion.
+/// Compare: Joe Sawada, Aaron Williams: Practical algorithms to rank necklaces, Lyndon words, and
+/// de Bruijn sequences. J. Discrete Algorithms 43: 95-110 (2017)
+fn lyndon_counts_below(alpha: &[u8], sigma: usize) -> Result<Vec<(usize, i128)>> {
+    let n = alpha.len();
+    checked_power(sigma, n)?;
+    let blocks: Vec<i128> = alpha
+        .iter()
+        .map(|&c| (sigma - 1 - c as usize) as i128)
+        .collect(); //@ the number of blocks of length j+1
+                    //@ concatenations[m]: the number of (linear) concatenations of blocks of total length m
+    let mut concatenations = vec![0_i128; n + 1];
+    concatenations[0] = 1;
+    for m in 1..=n {
+        concatenations[m] = (0..m).map(|j| blocks[j] * concatenations[m - j - 1]).sum();
+    }
+    let mut counts: Vec<(usize, i128)> = Vec::new();
+    let mut weighted = Vec::new(); //@ sigma^d minus the number of cyclic concatenations of length d
+    for d in divisors(n) {
+        //@ a cyclic concatenation has one block covering the first position, at one of its offsets
+        let cyclic: i128 = (0..d)
+            .map(|j| (j as i128 + 1) * blocks[j] * concatenations[d - j - 1])
+            .sum();
+        weighted.push((d, checked_power(sigma, d)? - cyclic));
+        let inverted: i128 = weighted
+            .iter()
+            .filter(|(e, _)| d.is_multiple_of(*e))
+            .map(|&(e, below)| moebius(d / e) * below)
+            .sum();
+        counts.push((d, inverted / d as i128));
+    }
+    Ok(counts)
+}
+
+/// the number of necklaces and Lyndon words of length n that are not larger than the prenecklace `alpha`
+fn counts_below(alpha: &[u8], sigma: usize) -> Result<(u128, u128)> {
+    let counts = lyndon_counts_below(alpha, sigma)?;
+    let necklaces: i128 = counts.iter().map(|&(_, count)| count).sum();
+    let lyndon_words = counts.last().unwrap().1;
+    Ok((necklaces as u128, lyndon_words as u128))
+}
+
+/// the rank of the necklace or Lyndon word `word` among the words of its kind and length over sigma letters in lexicographic order, starting with 0
+fn rank(word: &[u8], sigma: usize, lyndon: bool) -> Result<u128> {
+    check_sigma(sigma)?;
+    let kind = if lyndon { "Lyndon word" } else { "necklace" };
+    let period = prenecklace_period(word, sigma)?;
+    match period {
+        Some(p) if (lyndon && p == word.len()) || (!lyndon && word.len().is_multiple_of(p)) => (),
+        _ => {
+            return Err(Error::InvalidArgument(format!(
+                "{:?} is not a {}",
+                word, kind
+            )))
+        }
+    }
+    if word.is_empty() {
+        return Ok(0); //@ the only necklace of length 0
+    }
+    let (necklaces, lyndon_words) = counts_below(word, sigma)?;
+    Ok(if lyndon { lyndon_words } else { necklaces } - 1)
+}
+
+/// The word of length n of the given rank among the necklaces or Lyndon words over sigma letters.
+/// Determines the word letter by letter, choosing the smallest letter c for the prefix p such
+/// that more than `rank` words are not larger than p c z^{n-|p|-1} for the largest letter z.
+/// Only prefixes that are prenecklaces can be prefixes of necklaces.
+fn unrank(rank: u128, n: usize, sigma: usize, lyndon: bool) -> Result<Vec<u8>> {
+    check_sigma(sigma)?;
+    let total = if lyndon {
+        lyndon_count(sigma, n)?
+    } else {
+        necklace_count(sigma, n)?
+    };
+    if rank >= total {
+        return Err(Error::InvalidArgument(format!(
+            "the rank {} exceeds the number {} of {} of length {} over {} letters",
+            rank,
+            total,
+            if lyndon { "Lyndon words" } else { "necklaces" },
+            n,
+            sigma
+        )));
+    }
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    let z = (sigma - 1) as u8;
+    let mut word = vec![z; n];
+    for i in 0..n {
+        for c in 0..=z {
+            word[i] = c;
+            if prenecklace_period(&word[..=i], sigma)?.is_none() {
+                continue;
+            }
+            let (necklaces, lyndon_words) = counts_below(&word, sigma)?;
+            if rank < if lyndon { lyndon_words } else { necklaces } {
+                break;
+            }
+        }
+    }
+    Ok(word)
+}
+
+/// the rank of `necklace` among the necklaces of its length over sigma letters in lexicographic order, starting with 0
+pub fn necklace_rank(necklace: &[u8], sigma: usize) -> Result<u128> {
+    rank(necklace, sigma, false)
+}
+
+/// the necklace of length n over sigma letters with the given rank in lexicographic order
+pub fn necklace_unrank(rank: u128, n: usize, sigma: usize) -> Result<Vec<u8>> {
+    unrank(rank, n, sigma, false)
+}
+
+/// the rank of `word` among the Lyndon words of its length over sigma letters in lexicographic order, starting with 0
+pub fn lyndon_rank(word: &[u8], sigma: usize) -> Result<u128> {
+    rank(word, sigma, true)
+}
+
+/// the Lyndon word of length n over sigma letters with the given rank in lexicographic order
+pub fn lyndon_unrank(rank: u128, n: usize, sigma: usize) -> Result<Vec<u8>> {
+    unrank(rank, n, sigma, true)
+}
+
+#[cfg(test)]
+fn least_rotation_naive(word: &[u8]) -> Vec<u8> {
+    (0..word.len())
+        .map(|i| [&word[i..], &word[..i]].concat())
+        .min()
+        .unwrap()
+}
+
+#[test]
+fn test_necklace_generator() {
+    for sigma in 1..=4 {
+        for n in 1..=7 {
+            let words: Vec<Vec<u8>> =
+                itertools::Itertools::multi_cartesian_product((0..n).map(|_| 0..sigma as u8))
+                    .collect();
+            let filter = |predicate: &dyn Fn(&[u8]) -> bool| {
+                words
+                    .iter()
+                    .filter(|w| predicate(w))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            };
+            let generate = |kind| {
+                NecklaceGenerator::new(n, sigma, kind)
+                    .unwrap()
+                    .collect::<Vec<_>>()
+            };
+            let is_necklace = |w: &[u8]| least_rotation_naive(w) == w;
+            //@ a prenecklace extended by the largest letter up to length 2n is a necklace
+            let z = sigma as u8 - 1;
+            let prenecklaces = filter(&|w| is_necklace(&[w, &vec![z; n][..]].concat()));
+            let necklaces = filter(&is_necklace);
+            let lyndon_words = filter(&|w| is_necklace(w) && core::is_primitive(w));
+            let bracelets = filter(&|w| {
+                let mut reversal = w.to_vec();
+                reversal.reverse();
+                is_necklace(w) && w <= least_rotation_naive(&reversal).as_slice()
+            });
+            assert_eq!(generate(NecklaceKind::Prenecklace), prenecklaces);
+            assert_eq!(generate(NecklaceKind::Necklace), necklaces);
+            assert_eq!(generate(NecklaceKind::Lyndon), lyndon_words);
+            assert_eq!(generate(NecklaceKind::Bracelet), bracelets);
+
+            assert_eq!(
+                prenecklace_count(sigma, n).unwrap(),
+                prenecklaces.len() as u128
+            );
+            assert_eq!(necklace_count(sigma, n).unwrap(), necklaces.len() as u128);
+            assert_eq!(lyndon_count(sigma, n).unwrap(), lyndon_words.len() as u128);
+            assert_eq!(bracelet_count(sigma, n).unwrap(), bracelets.len() as u128);
+        }
+    }
+    //@ the empty word is the only prenecklace, necklace and bracelet of length 0
+    for sigma in 0..=2 {
+        for kind in [
+            NecklaceKind::Prenecklace,
+            NecklaceKind::Necklace,
+            NecklaceKind::Lyndon,
+            NecklaceKind::Bracelet,
+        ] {
+            let words: Vec<Vec<u8>> = NecklaceGenerator::new(0, sigma, kind).unwrap().collect();
+            let expected = if kind == NecklaceKind::Lyndon { 0 } else { 1 };
+            assert_eq!(words.len(), expected);
+            assert!(words.iter().all(Vec::is_empty));
+        }
+    }
+    //@ the Lyndon words of all lengths are those enumerated by LyndonWordGenerator
+    let mut lyndon_words: Vec<Vec<u8>> = (1..=6)
+        .flat_map(|n| NecklaceGenerator::new(n, 3, NecklaceKind::Lyndon).unwrap())
+        .collect();
+    lyndon_words.sort();
+    assert_eq!(
+        lyndon_words,
+        core::LyndonWordGenerator::new(6, 3).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_counts() {
+    //@ https://oeis.org/A000031, https://oeis.org/A001037, https://oeis.org/A000029
+    let necklaces = [1, 2, 3, 4, 6, 8, 14, 20, 36, 60, 108];
+    let lyndon_words = [0, 2, 1, 2, 3, 6, 9, 18, 30, 56, 99];
+    let bracelets = [1, 2, 3, 4, 6, 8, 13, 18, 30, 46, 78];
+    for n in 0..necklaces.len() {
+        assert_eq!(necklace_count(2, n).unwrap(), necklaces[n]);
+        assert_eq!(lyndon_count(2, n).unwrap(), lyndon_words[n]);
+        assert_eq!(bracelet_count(2, n).unwrap(), bracelets[n]);
+    }
+    assert_eq!(
+        lyndon_count(26, 20).unwrap(),
+        996_407_444_760_463_399_262_204_385
+    );
+    assert!(lyndon_count(2, 200).is_err());
+}
+
+#[test]
+fn test_rank_unrank() {
+    for sigma in 1..=4 {
+        for n in 1..=7 {
+            for (kind, lyndon) in [
+                (NecklaceKind::Necklace, false),
+                (NecklaceKind::Lyndon, true),
+            ] {
+                for (r, word) in NecklaceGenerator::new(n, sigma, kind).unwrap().enumerate() {
+                    assert_eq!(rank(&word, sigma, lyndon).unwrap(), r as u128);
+                    assert_eq!(unrank(r as u128, n, sigma, lyndon).unwrap(), word);
+                }
+            }
+        }
+    }
+    for (r, word) in NecklaceGenerator::new(24, 2, NecklaceKind::Lyndon)
+        .unwrap()
+        .enumerate()
+        .step_by(10_007)
+    {
+        assert_eq!(lyndon_rank(&word, 2).unwrap(), r as u128);
+        assert_eq!(lyndon_unrank(r as u128, 24, 2).unwrap(), word);
+    }
+    //@ the letters must fit into bytes
+    assert!(NecklaceGenerator::new(1, 258, NecklaceKind::Necklace).is_err());
+    assert!(necklace_unrank(0, 1, 258).is_err());
+    assert!(lyndon_rank(&[0], 258).is_err());
+    let last = lyndon_count(2, 30).unwrap() - 1;
+    assert_eq!(
+        lyndon_unrank(last, 30, 2).unwrap(),
+        [&[0_u8][..], &[1; 29]].concat()
+    );
+    assert!(lyndon_unrank(last + 1, 30, 2).is_err());
+    assert!(necklace_rank(&[1, 0], 2).is_err());
+    assert!(lyndon_rank(&[0, 0], 2).is_err());
+    assert!(necklace_rank(&[0, 2], 2).is_err());
+    //@ the empty word is the only necklace of length 0, but not a Lyndon word
+    for sigma in 0..=2 {
+        assert_eq!(necklace_rank(&[], sigma).unwrap(), 0);
+        assert_eq!(necklace_unrank(0, 0, sigma).unwrap(), Vec::<u8>::new());
+        assert!(necklace_unrank(1, 0, sigma).is_err());
+        assert!(lyndon_rank(&[], sigma).is_err());
+        assert!(lyndon_unrank(0, 0, sigma).is_err());
+    }
+}
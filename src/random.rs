@@ -0,0 +1,338 @@
+//! Generators of random strings with controlled statistics, drawing from a given random number
+//! generator such that a seeded generator reproduces the same strings.
+use crate::error::{Error, Result};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+const ALPHANUMERIC: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// the first `sigma` letters of a-z, A-Z, 0-9, or all of them if `sigma` is 0
+pub fn alphanumeric_alphabet(sigma: usize) -> Result<&'static [u8]> {
+    match sigma {
+        0 => Ok(ALPHANUMERIC),
+        _ => ALPHANUMERIC.get(..sigma).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "the alphabet size {} exceeds the {} alphanumeric letters",
+                sigma,
+                ALPHANUMERIC.len()
+            ))
+        }),
+    }
+}
+
+/// a string of `length` letters drawn uniformly and independently from `alphabet`
+pub fn uniform_string<R: Rng>(rng: &mut R, alphabet: &[u8], length: usize) -> Result<Vec<u8>> {
+    if alphabet.is_empty() {
+        return Err(Error::InvalidArgument(String::from(
+            "the alphabet must not be empty",
+        )));
+    }
+    Ok((0..length)
+        .map(|_| alphabet[rng.gen_range(0..alphabet.len())])
+        .collect())
+}
+
+/// A zeroth-order source drawing each letter independently with a given probability.
+#[derive(Clone, Debug)]
+pub struct ZerothOrderSource {
+    letters: Vec<u8>,
+    distribution: WeightedIndex<f64>,
+}
+
+impl ZerothOrderSource {
+    /// The source drawing each letter with a probability proportional to its weight.
+    pub fn new(weights: &[(u8, f64)]) -> Result<ZerothOrderSource> {
+        //@ WeightedIndex panics instead of failing on infinite weights or sums
+        if let Some(&(letter, weight)) = weights.iter().find(|(_, weight)| !weight.is_finite()) {
+            return Err(Error::InvalidArgument(format!(
+                "the weight {} of the letter '{}' is not finite",
+                weight, letter as char
+            )));
+        }
+        if !weights
+            .iter()
+            .map(|&(_, weight)| weight)
+            .sum::<f64>()
+            .is_finite()
+        {
+            return Err(Error::InvalidArgument(String::from(
+                "the sum of the letter weights is not finite",
+            )));
+        }
+        let distribution = WeightedIndex::new(weights.iter().map(|&(_, weight)| weight))
+            .map_err(|e| Error::InvalidArgument(format!("invalid letter weights: {}", e)))?;
+        Ok(ZerothOrderSource {
+            letters: weights.iter().map(|&(letter, _)| letter).collect(),
+            distribution,
+        })
+    }
+
+    /// Parses weights `letter:weight` separated by commas, like `a:0.5,b:0.25,c:0.25`.
+    pub fn parse(spec: &str) -> Result<ZerothOrderSource> {
+        let mut weights = Vec::new();
+        for entry in spec.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let invalid = || {
+                Error::InvalidArgument(format!(
+                    "the weight '{}' is not of the form letter:weight",
+                    entry
+                ))
+            };
+            let (letter, weight) = entry.rsplit_once(':').ok_or_else(invalid)?;
+            let letter = match letter.as_bytes() {
+                [letter] => *letter,
+                _ => return Err(invalid()),
+            };
+            if weights.iter().any(|&(c, _)| c == letter) {
+                return Err(Error::InvalidArgument(format!(
+                    "the letter '{}' has more than one weight",
+                    letter as char
+                )));
+            }
+            weights.push((letter, weight.trim().parse::<f64>().map_err(|_| invalid())?));
+        }
+        ZerothOrderSource::new(&weights)
+    }
+
+    /// a string of `length` letters drawn independently from the source
+    pub fn string<R: Rng>(&self, rng: &mut R, length: usize) -> Vec<u8> {
+        (0..length)
+            .map(|_| self.letters[self.distribution.sample(rng)])
+            .collect()
+    }
+}
+
+/// A k-th order Markov source drawing each letter with the empirical probability that it follows
+/// the preceding k letters in a training text. The training text is read cyclically, such that
+/// each of its contexts has a successor.
+#[derive(Clone, Debug)]
+pub struct MarkovSource {
+    order: usize,
+    //@ BTreeMap instead of HashMap such that the choice of the initial context is reproducible
+    contexts: BTreeMap<Vec<u8>, (Vec<u8>, WeightedIndex<usize>)>, //@ the successors of a context with their frequencies
+}
+
+impl MarkovSource {
+    /// trains the source of order `order` on the non-empty text `text`
+    pub fn train(text: &[u8], order: usize) -> Result<MarkovSource> {
+        if text.is_empty() {
+            return Err(Error::InvalidInput(String::from(
+                "the training text of the Markov source is empty",
+            )));
+        }
+        let n = text.len();
+        let mut counts = BTreeMap::<Vec<u8>, BTreeMap<u8, usize>>::new();
+        for i in 0..n {
+            let context = (i..i + order).map(|j| text[j % n]).collect();
+            *counts
+                .entry(context)
+                .or_default()
+                .entry(text[(i + order) % n])
+                .or_default() += 1;
+        }
+        let contexts = counts
+            .into_iter()
+            .map(|(context, successors)| {
+                let letters = successors.keys().copied().collect();
+                let distribution = WeightedIndex::new(successors.values()).unwrap();
+                (context, (letters, distribution))
+            })
+            .collect();
+        Ok(MarkovSource { order, contexts })
+    }
+
+    /// the order k of the source, i.e., the length of the contexts
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    /// A string of `length` letters starting with a context of the training text chosen uniformly
+    /// at random, continued letter by letter according to the preceding k letters.
+    pub fn string<R: Rng>(&self, rng: &mut R, length: usize) -> Vec<u8> {
+        let initial = rng.gen_range(0..self.contexts.len());
+        let mut text = self.contexts.keys().nth(initial).unwrap().clone();
+        text.truncate(length);
+        while text.len() < length {
+            //@ each context of the output is a context of the cyclic training text
+            let context = &text[text.len() - self.order..];
+            let (letters, distribution) = &self.contexts[context];
+            text.push(letters[distribution.sample(rng)]);
+        }
+        text
+    }
+}
+
+/// Mutates a copy of `base`: each letter is, with probability `rate`, substituted by another
+/// letter of `alphabet`, deleted, or followed by an inserted letter of `alphabet`, where each kind
+/// of mutation is equally likely.
+fn mutate<R: Rng>(rng: &mut R, base: &[u8], alphabet: &[u8], rate: f64) -> Vec<u8> {
+    let mut copy = Vec::with_capacity(base.len());
+    for &c in base {
+        if !rng.gen_bool(rate) {
+            copy.push(c);
+            continue;
+        }
+        match rng.gen_range(0..3) {
+            0 => {
+                let others: Vec<u8> = alphabet.iter().copied().filter(|&d| d != c).collect();
+                copy.push(if others.is_empty() {
+                    c
+                } else {
+                    others[rng.gen_range(0..others.len())]
+                });
+            }
+            1 => (), //@ deletion
+            _ => {
+                copy.push(c);
+                copy.push(alphabet[rng.gen_range(0..alphabet.len())]);
+            }
+        }
+    }
+    copy
+}
+
+/// A repetitive string of `length` letters: `base` followed by copies of `base`, each mutated
+/// independently by `mutate` with the mutation rate `rate` over the letters of `base`.
+pub fn repetitive_string<R: Rng>(
+    rng: &mut R,
+    base: &[u8],
+    rate: f64,
+    length: usize,
+) -> Result<Vec<u8>> {
+    if base.is_empty() {
+        return Err(Error::InvalidInput(String::from(
+            "the base string of the repetitive string is empty",
+        )));
+    }
+    if !(0.0..=1.0).contains(&rate) {
+        return Err(Error::InvalidArgument(format!(
+            "the mutation rate {} is not in [0, 1]",
+            rate
+        )));
+    }
+    let mut alphabet = base.to_vec();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+
+    let mut text = Vec::with_capacity(length);
+    text.extend_from_slice(&base[..base.len().min(length)]);
+    while text.len() < length {
+        let copy = mutate(rng, base, &alphabet, rate);
+        let take = copy.len().min(length - text.len());
+        text.extend_from_slice(&copy[..take]);
+    }
+    Ok(text)
+}
+
+#[cfg(test)]
+use rand::{rngs::StdRng, SeedableRng};
+
+#[test]
+fn test_uniform_string() {
+    let alphabet = alphanumeric_alphabet(4).unwrap();
+    assert_eq!(alphabet, b"abcd");
+    assert_eq!(alphanumeric_alphabet(0).unwrap().len(), 62);
+    assert!(alphanumeric_alphabet(63).is_err());
+
+    let text = uniform_string(&mut StdRng::seed_from_u64(1), alphabet, 1000).unwrap();
+    assert_eq!(text.len(), 1000);
+    assert!(text.iter().all(|c| alphabet.contains(c)));
+    //@ the same seed gives the same string
+    assert_eq!(
+        text,
+        uniform_string(&mut StdRng::seed_from_u64(1), alphabet, 1000).unwrap()
+    );
+    assert_ne!(
+        text,
+        uniform_string(&mut StdRng::seed_from_u64(2), alphabet, 1000).unwrap()
+    );
+    assert!(uniform_string(&mut StdRng::seed_from_u64(1), b"", 1).is_err());
+}
+
+#[test]
+fn test_zeroth_order_source() {
+    let source = ZerothOrderSource::parse("a:0.7, b:0.2,c:0.1").unwrap();
+    let text = source.string(&mut StdRng::seed_from_u64(3), 100_000);
+    let frequency = |c: u8| text.iter().filter(|&&d| d == c).count() as f64 / text.len() as f64;
+    approx::assert_abs_diff_eq!(frequency(b'a'), 0.7, epsilon = 0.01);
+    approx::assert_abs_diff_eq!(frequency(b'b'), 0.2, epsilon = 0.01);
+    approx::assert_abs_diff_eq!(frequency(b'c'), 0.1, epsilon = 0.01);
+    //@ a colon can be a letter
+    let colons = ZerothOrderSource::parse(":: 1").unwrap();
+    assert_eq!(colons.string(&mut StdRng::seed_from_u64(3), 3), b":::");
+    for spec in [
+        "",
+        "a:0",
+        "a:-1,b:2",
+        "a:1,a:2",
+        "ab:1",
+        "a=1",
+        "a:x",
+        "a:inf",
+        "a:NaN",
+        "a:1e308,b:1e308",
+    ] {
+        assert!(ZerothOrderSource::parse(spec).is_err(), "{}", spec);
+    }
+}
+
+#[test]
+fn test_markov_source() {
+    //@ each letter of abc determines its successor
+    let source = MarkovSource::train(b"abc", 1).unwrap();
+    let text = source.string(&mut StdRng::seed_from_u64(4), 10);
+    let start = (text[0] - b'a') as usize;
+    assert!(text
+        .iter()
+        .copied()
+        .eq(b"abc".iter().copied().cycle().skip(start).take(10)));
+
+    let training = crate::word::fibonacci_word(10);
+    for order in 0..6 {
+        let source = MarkovSource::train(&training, order).unwrap();
+        assert_eq!(source.order(), order);
+        let text = source.string(&mut StdRng::seed_from_u64(order as u64), 1000);
+        assert_eq!(text.len(), 1000);
+        //@ all (k+1)-grams of the output occur in the cyclic training text
+        let mut cyclic = training.clone();
+        cyclic.extend_from_slice(&training[..order]);
+        for gram in text.windows(order + 1) {
+            assert!(cyclic.windows(order + 1).any(|g| g == gram));
+        }
+    }
+    assert!(
+        MarkovSource::train(b"ab", 5)
+            .unwrap()
+            .string(&mut StdRng::seed_from_u64(0), 2)
+            .len()
+            == 2
+    );
+    assert!(MarkovSource::train(b"", 1).is_err());
+}
+
+#[test]
+fn test_repetitive_string() {
+    let base = b"abracadabra";
+    let text = repetitive_string(&mut StdRng::seed_from_u64(5), base, 0.0, 50).unwrap();
+    assert!(text.iter().eq(base.iter().cycle().take(50)));
+
+    let text = repetitive_string(&mut StdRng::seed_from_u64(5), base, 0.1, 10_000).unwrap();
+    assert_eq!(text.len(), 10_000);
+    assert_eq!(&text[..base.len()], base);
+    assert!(text.iter().all(|c| base.contains(c)));
+    assert_eq!(
+        text,
+        repetitive_string(&mut StdRng::seed_from_u64(5), base, 0.1, 10_000).unwrap()
+    );
+    //@ mutated copies keep the string repetitive, i.e., with far fewer distinct factors than a uniform string
+    let distinct = |text: &[u8]| {
+        text.windows(8)
+            .collect::<std::collections::HashSet<&[u8]>>()
+            .len()
+    };
+    let uniform = uniform_string(&mut StdRng::seed_from_u64(5), b"abcdr", 10_000).unwrap();
+    assert!(4 * distinct(&text) < distinct(&uniform));
+
+    assert!(repetitive_string(&mut StdRng::seed_from_u64(5), b"", 0.1, 10).is_err());
+    assert!(repetitive_string(&mut StdRng::seed_from_u64(5), base, 1.5, 10).is_err());
+}
@@ -0,0 +1,309 @@
+/// a parameter or a measured value of a result record
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(u64),
+    Float(f64),
+    Bool(bool),
+    Text(String),
+}
+
+impl From<usize> for Value {
+    fn from(x: usize) -> Value {
+        Value::Integer(x as u64)
+    }
+}
+
+impl From<u64> for Value {
+    fn from(x: u64) -> Value {
+        Value::Integer(x)
+    }
+}
+
+impl From<u32> for Value {
+    fn from(x: u32) -> Value {
+        Value::Integer(x as u64)
+    }
+}
+
+impl From<u8> for Value {
+    fn from(x: u8) -> Value {
+        Value::Integer(x as u64)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(x: f64) -> Value {
+        Value::Float(x)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(x: bool) -> Value {
+        Value::Bool(x)
+    }
+}
+
+impl From<String> for Value {
+    fn from(x: String) -> Value {
+        Value::Text(x)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(x: &str) -> Value {
+        Value::Text(x.to_string())
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Integer(x) => write!(f, "{}", x),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Bool(x) => write!(f, "{}", x),
+            Value::Text(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+/// the output formats of result records
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// `RESULT key=value ...` lines as read by sqlplot
+    Result,
+    /// one JSON object per line
+    Json,
+    /// comma-separated values with a header line
+    Csv,
+}
+
+/// The result of running an algorithm on an input, with a fixed schema:
+/// the tool and algorithm names, the input name, the input length n, the parameters and
+/// measured values in the order they were added, the running time, and the peak memory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResultRecord {
+    pub tool: String,
+    pub algorithm: String,
+    pub input: String,
+    pub n: usize,
+    pub values: Vec<(String, Value)>,
+    pub time_ms: u128,
+    /// the peak resident set size in kilobytes, if available
+    pub peak_memory_kb: Option<u64>,
+}
+
+impl ResultRecord {
+    pub fn new(tool: &str, algorithm: &str, input: &str, n: usize) -> ResultRecord {
+        ResultRecord {
+            tool: tool.to_string(),
+            algorithm: algorithm.to_string(),
+            input: input.to_string(),
+            n,
+            values: Vec::new(),
+            time_ms: 0,
+            peak_memory_kb: None,
+        }
+    }
+
+    /// appends a parameter or a measured value
+    pub fn with<V: Into<Value>>(mut self, key: &str, value: V) -> ResultRecord {
+        self.push(key, value);
+        self
+    }
+
+    pub fn push<V: Into<Value>>(&mut self, key: &str, value: V) {
+        self.values.push((key.to_string(), value.into()));
+    }
+
+    /// sets the running time to the time elapsed since `start`
+    pub fn timed(mut self, start: &std::time::Instant) -> ResultRecord {
+        self.time_ms = start.elapsed().as_millis();
+        self
+    }
+
+    /// the keys and values of all fields in the order of the schema
+    fn fields(&self) -> Vec<(&str, Value)> {
+        let mut fields = vec![
+            ("tool", Value::from(self.tool.as_str())),
+            ("algo", Value::from(self.algorithm.as_str())),
+            ("input", Value::from(self.input.as_str())),
+            ("n", Value::from(self.n)),
+        ];
+        fields.extend(self.values.iter().map(|(k, v)| (k.as_str(), v.clone())));
+        fields.push(("time_ms", Value::Integer(self.time_ms as u64)));
+        if let Some(peak) = self.peak_memory_kb {
+            fields.push(("peak_memory_kb", Value::Integer(peak)));
+        }
+        fields
+    }
+
+    /// the sqlplot line `RESULT key=value ...`, where whitespace in values is replaced by '_'
+    pub fn to_result_line(&self) -> String {
+        let mut line = String::from("RESULT");
+        for (key, value) in self.fields() {
+            let value: String = value
+                .to_string()
+                .chars()
+                .map(|c| if c.is_whitespace() { '_' } else { c })
+                .collect();
+            line.push_str(&format!(" {}={}", key, value));
+        }
+        line
+    }
+
+    /// a JSON object on a single line
+    pub fn to_json(&self) -> String {
+        let fields: Vec<String> = self
+            .fields()
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    Value::Integer(x) => x.to_string(),
+                    Value::Float(x) if x.is_finite() => x.to_string(),
+                    Value::Float(_) => String::from("null"),
+                    Value::Bool(x) => x.to_string(),
+                    Value::Text(x) => json_string(x),
+                };
+                format!("{}:{}", json_string(key), value)
+            })
+            .collect();
+        format!("{{{}}}", fields.join(","))
+    }
+
+    /// the CSV header line naming the fields of this record
+    pub fn csv_header(&self) -> String {
+        let keys: Vec<String> = self
+            .fields()
+            .iter()
+            .map(|(key, _)| csv_field(key))
+            .collect();
+        keys.join(",")
+    }
+
+    /// the CSV line of the values of this record
+    pub fn to_csv(&self) -> String {
+        let values: Vec<String> = self
+            .fields()
+            .iter()
+            .map(|(_, value)| csv_field(&value.to_string()))
+            .collect();
+        values.join(",")
+    }
+}
+
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// the peak resident set size of this process in kilobytes, read from /proc on Linux
+pub fn peak_memory_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+/// Resets the peak resident set size of this process to its current size, on Linux, and returns
+/// whether this succeeded.
+pub fn reset_peak_memory() -> bool {
+    std::fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// Prints result records in a given format. For CSV, the header is printed before the first
+/// record, and again whenever the fields change.
+pub struct Reporter {
+    format: OutputFormat,
+    header: Option<String>,
+    peak_memory: bool,
+}
+
+impl Reporter {
+    pub fn new(format: OutputFormat) -> Reporter {
+        Reporter {
+            format,
+            header: None,
+            peak_memory: true,
+        }
+    }
+
+    /// a reporter that does not fill in the peak memory of records without it
+    pub fn without_peak_memory(self) -> Reporter {
+        Reporter {
+            peak_memory: false,
+            ..self
+        }
+    }
+
+    /// the lines to output for `record`
+    pub fn format(&mut self, record: &ResultRecord) -> String {
+        match self.format {
+            OutputFormat::Result => record.to_result_line(),
+            OutputFormat::Json => record.to_json(),
+            OutputFormat::Csv => {
+                let header = record.csv_header();
+                if self.header.as_ref() == Some(&header) {
+                    record.to_csv()
+                } else {
+                    let lines = format!("{}\n{}", header, record.to_csv());
+                    self.header = Some(header);
+                    lines
+                }
+            }
+        }
+    }
+
+    /// prints `record` to stdout, filling in the peak memory if it is not set, unless the reporter
+    /// is [`without_peak_memory`](Reporter::without_peak_memory)
+    pub fn emit(&mut self, mut record: ResultRecord) {
+        if self.peak_memory && record.peak_memory_kb.is_none() {
+            record.peak_memory_kb = peak_memory_kb();
+        }
+        println!("{}", self.format(&record));
+    }
+}
+
+#[test]
+fn test_result_record() {
+    let record = ResultRecord::new("count_r", "bwt", "my file.txt", 10)
+        .with("bwt_runs", 4_usize)
+        .with("entropy", 0.5)
+        .with("name", "a,\"b\"");
+    assert_eq!(
+        record.to_result_line(),
+        "RESULT tool=count_r algo=bwt input=my_file.txt n=10 bwt_runs=4 entropy=0.5 name=a,\"b\" time_ms=0"
+    );
+    assert_eq!(
+        record.to_json(),
+        "{\"tool\":\"count_r\",\"algo\":\"bwt\",\"input\":\"my file.txt\",\"n\":10,\"bwt_runs\":4,\"entropy\":0.5,\"name\":\"a,\\\"b\\\"\",\"time_ms\":0}"
+    );
+    let mut reporter = Reporter::new(OutputFormat::Csv);
+    assert_eq!(
+        reporter.format(&record),
+        "tool,algo,input,n,bwt_runs,entropy,name,time_ms\ncount_r,bwt,my file.txt,10,4,0.5,\"a,\"\"b\"\"\",0"
+    );
+    assert_eq!(
+        reporter.format(&record),
+        "count_r,bwt,my file.txt,10,4,0.5,\"a,\"\"b\"\"\",0"
+    );
+}
@@ -0,0 +1,360 @@
+extern crate cdivsufsort;
+use crate::error::{Error, Result};
+use crate::fmindex::ByteRank;
+
+/// The run-length encoded BWT, storing for each of the r runs its head character and its
+/// starting position, and for each character the runs it heads with their cumulative lengths.
+/// All components take O(r) words.
+pub struct RunLengthBWT {
+    /// the character of each run
+    heads: Vec<u8>,
+    /// the starting position of each run, followed by the length of the BWT
+    run_starts: Vec<usize>,
+    /// char_runs[c] lists the indices of the runs with head c
+    char_runs: Vec<Vec<usize>>,
+    /// char_cumulative[c][j] is the total length of the first j runs with head c
+    char_cumulative: Vec<Vec<usize>>,
+}
+
+impl RunLengthBWT {
+    /// number of runs r
+    pub fn number_of_runs(&self) -> usize {
+        self.heads.len()
+    }
+
+    /// the index of the run containing BWT position `i`
+    pub fn run_of(&self, i: usize) -> usize {
+        debug_assert!(i < self.len());
+        self.run_starts.partition_point(|&start| start <= i) - 1
+    }
+
+    /// the starting position of the run with index `run`
+    pub fn run_start(&self, run: usize) -> usize {
+        self.run_starts[run]
+    }
+
+    /// the last position of the run with index `run`
+    pub fn run_end(&self, run: usize) -> usize {
+        self.run_starts[run + 1] - 1
+    }
+
+    /// the head character of the run with index `run`
+    pub fn run_head(&self, run: usize) -> u8 {
+        self.heads[run]
+    }
+
+    /// the number of runs with head `c` among the first `run` runs
+    fn rank_heads(&self, c: u8, run: usize) -> usize {
+        self.char_runs[c as usize].partition_point(|&x| x < run)
+    }
+
+    /// the position of the (k+1)-th occurrence of `c` in the BWT, if it exists
+    pub fn select(&self, c: u8, k: usize) -> Option<usize> {
+        let cumulative = &self.char_cumulative[c as usize];
+        if k >= *cumulative.last().unwrap() {
+            return None;
+        }
+        let j = cumulative.partition_point(|&x| x <= k) - 1;
+        let run = self.char_runs[c as usize][j];
+        Some(self.run_starts[run] + k - cumulative[j])
+    }
+
+    /// the index of the last run with head `c` that starts before position `i`, if it exists
+    pub fn preceding_run(&self, c: u8, i: usize) -> Option<usize> {
+        let run = self.run_of(i);
+        let j = self.rank_heads(c, run + 1);
+        if j == 0 {
+            None
+        } else {
+            Some(self.char_runs[c as usize][j - 1])
+        }
+    }
+
+    /// space in machine words
+    pub fn size_in_words(&self) -> usize {
+        let word_bytes = std::mem::size_of::<usize>();
+        self.heads.len().div_ceil(word_bytes)
+            + self.run_starts.len()
+            + self.char_runs.iter().map(|runs| runs.len()).sum::<usize>()
+            + self
+                .char_cumulative
+                .iter()
+                .map(|cumulative| cumulative.len())
+                .sum::<usize>()
+    }
+}
+
+impl ByteRank for RunLengthBWT {
+    fn from_bwt(bwt: &[u8]) -> Self {
+        let mut heads = Vec::new();
+        let mut run_starts = Vec::new();
+        let mut char_runs = vec![Vec::new(); u8::MAX as usize + 1];
+        let mut char_cumulative = vec![vec![0_usize]; u8::MAX as usize + 1];
+        for (i, &c) in bwt.iter().enumerate() {
+            if i == 0 || bwt[i - 1] != c {
+                char_runs[c as usize].push(heads.len());
+                heads.push(c);
+                run_starts.push(i);
+                let total = *char_cumulative[c as usize].last().unwrap();
+                char_cumulative[c as usize].push(total);
+            }
+            *char_cumulative[c as usize].last_mut().unwrap() += 1;
+        }
+        run_starts.push(bwt.len());
+        RunLengthBWT {
+            heads,
+            run_starts,
+            char_runs,
+            char_cumulative,
+        }
+    }
+
+    fn rank(&self, c: u8, i: usize) -> usize {
+        if i == self.len() {
+            return *self.char_cumulative[c as usize].last().unwrap();
+        }
+        let run = self.run_of(i);
+        let j = self.rank_heads(c, run);
+        let preceding = self.char_cumulative[c as usize][j];
+        if self.heads[run] == c {
+            preceding + i - self.run_starts[run]
+        } else {
+            preceding
+        }
+    }
+
+    fn access(&self, i: usize) -> u8 {
+        self.heads[self.run_of(i)]
+    }
+
+    fn len(&self) -> usize {
+        *self.run_starts.last().unwrap()
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.size_in_words() * std::mem::size_of::<usize>()
+    }
+}
+
+/// The r-index, consisting of the run-length encoded BWT, the suffix array samples at the ends
+/// of the BWT runs, and the samples at the run boundaries for computing the function phi.
+/// A zero byte is appended as sentinel to the text, which therefore must not contain zero bytes.
+/// Reference: Travis Gagie, Gonzalo Navarro, Nicola Prezza: "Fully Functional Suffix Trees and
+/// Optimal Text Searching in BWT-Runs Bounded Space". J. ACM 67(1): 2:1-2:54 (2020)
+pub struct RIndex {
+    rlbwt: RunLengthBWT,
+    /// c_array[c] is the number of characters in the text smaller than c
+    c_array: Vec<usize>,
+    /// the suffix array value at the last position of each run
+    samples_last: Vec<usize>,
+    /// the suffix array values at the starting positions of the runs (except the first run),
+    /// sorted increasingly
+    phi_keys: Vec<usize>,
+    /// phi_values[k] is the suffix array value preceding the suffix array value phi_keys[k]
+    phi_values: Vec<usize>,
+}
+
+impl RIndex {
+    /// builds the r-index of `text`
+    pub fn new(text: &[u8]) -> Result<RIndex> {
+        if text.contains(&0) {
+            return Err(Error::InvalidInput(String::from(
+                "the input text contains bytes equal to zero",
+            )));
+        }
+        let mut text = text.to_vec();
+        text.push(0u8);
+        let n = text.len();
+        let sa = {
+            let mut sa = vec![0; n];
+            cdivsufsort::sort_in_place(&text, sa.as_mut_slice());
+            sa
+        };
+        let bwt: Vec<u8> = sa
+            .iter()
+            .map(|&value| text[(n + value as usize - 1) % n])
+            .collect();
+        let rlbwt = RunLengthBWT::from_bwt(&bwt);
+
+        let mut c_array = vec![0_usize; u8::MAX as usize + 2];
+        for &c in text.iter() {
+            c_array[c as usize + 1] += 1;
+        }
+        for c in 1..c_array.len() {
+            c_array[c] += c_array[c - 1];
+        }
+
+        let r = rlbwt.number_of_runs();
+        let samples_last: Vec<usize> = (0..r).map(|run| sa[rlbwt.run_end(run)] as usize).collect();
+        let mut phi_pairs: Vec<(usize, usize)> = (1..r)
+            .map(|run| (sa[rlbwt.run_start(run)] as usize, samples_last[run - 1]))
+            .collect();
+        phi_pairs.sort_unstable();
+        Ok(RIndex {
+            rlbwt,
+            c_array,
+            samples_last,
+            phi_keys: phi_pairs.iter().map(|pair| pair.0).collect(),
+            phi_values: phi_pairs.iter().map(|pair| pair.1).collect(),
+        })
+    }
+
+    /// length of the indexed text, including the sentinel
+    pub fn len(&self) -> usize {
+        self.rlbwt.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rlbwt.is_empty()
+    }
+
+    /// number of runs in the BWT
+    pub fn number_of_runs(&self) -> usize {
+        self.rlbwt.number_of_runs()
+    }
+
+    pub fn rlbwt(&self) -> &RunLengthBWT {
+        &self.rlbwt
+    }
+
+    /// The suffix array interval [begin, end) of all suffixes prefixed by `pattern`, and the
+    /// suffix array value at position end-1 (the toehold), if the pattern occurs.
+    pub fn backward_search(&self, pattern: &[u8]) -> Option<(usize, usize, usize)> {
+        if pattern.contains(&0) {
+            return None; //@ the sentinel does not occur in the text, and its toehold would underflow
+        }
+        let mut begin = 0;
+        let mut end = self.len();
+        let mut toehold = self.samples_last[self.rlbwt.number_of_runs() - 1];
+        for &c in pattern.iter().rev() {
+            if self.rlbwt.access(end - 1) == c {
+                toehold -= 1;
+            } else {
+                //@ the last occurrence of c in [begin, end) is at the end of a run headed by c
+                let run = self.rlbwt.preceding_run(c, end - 1)?;
+                if self.rlbwt.run_end(run) < begin {
+                    return None;
+                }
+                toehold = self.samples_last[run] - 1;
+            }
+            begin = self.c_array[c as usize] + self.rlbwt.rank(c, begin);
+            end = self.c_array[c as usize] + self.rlbwt.rank(c, end);
+            if begin >= end {
+                return None;
+            }
+        }
+        Some((begin, end, toehold))
+    }
+
+    /// number of occurrences of `pattern` in the text
+    pub fn count(&self, pattern: &[u8]) -> usize {
+        match self.backward_search(pattern) {
+            None => 0,
+            Some((begin, end, _)) => end - begin,
+        }
+    }
+
+    /// the function phi mapping SA[i] to SA[i-1]
+    pub fn phi(&self, text_position: usize) -> usize {
+        let k = self.phi_keys.partition_point(|&key| key <= text_position) - 1;
+        self.phi_values[k] + text_position - self.phi_keys[k]
+    }
+
+    /// the starting positions of all occurrences of `pattern` in the text, in reverse suffix
+    /// array order
+    pub fn locate(&self, pattern: &[u8]) -> Vec<usize> {
+        match self.backward_search(pattern) {
+            None => Vec::new(),
+            Some((begin, end, toehold)) => {
+                let mut positions = Vec::with_capacity(end - begin);
+                positions.push(toehold);
+                for _ in begin + 1..end {
+                    positions.push(self.phi(*positions.last().unwrap()));
+                }
+                positions
+            }
+        }
+    }
+
+    /// space in machine words
+    pub fn size_in_words(&self) -> usize {
+        self.rlbwt.size_in_words()
+            + self.c_array.len()
+            + self.samples_last.len()
+            + self.phi_keys.len()
+            + self.phi_values.len()
+    }
+}
+
+#[test]
+fn test_rlbwt() {
+    for text in crate::core::RandomStringGenerator::new(0..1024, 2) {
+        let rlbwt = RunLengthBWT::from_bwt(&text);
+        assert_eq!(rlbwt.len(), text.len());
+        assert_eq!(
+            rlbwt.number_of_runs(),
+            crate::core::number_of_runs(&mut text.as_slice()).unwrap()
+        );
+        for (i, &c) in text.iter().enumerate() {
+            assert_eq!(rlbwt.access(i), c);
+        }
+        for c in 0..6 {
+            let mut count = 0;
+            for i in 0..=text.len() {
+                assert_eq!(rlbwt.rank(c, i), count);
+                if i < text.len() && text[i] == c {
+                    assert_eq!(rlbwt.select(c, count), Some(i));
+                    count += 1;
+                }
+            }
+            assert_eq!(rlbwt.select(c, count), None);
+        }
+    }
+}
+
+#[test]
+fn test_rindex() {
+    for mut text in crate::core::RandomStringGenerator::new(0..1024, 2) {
+        text.pop(); //@ remove the 0 byte
+        let index = RIndex::new(&text).unwrap();
+        for i in 0..text.len() {
+            for j in i + 1..std::cmp::min(text.len(), i + 5) {
+                let pattern = &text[i..j];
+                let naive: Vec<usize> = (0..text.len() - pattern.len() + 1)
+                    .filter(|&k| &text[k..k + pattern.len()] == pattern)
+                    .collect();
+                assert_eq!(index.count(pattern), naive.len());
+                let mut located = index.locate(pattern);
+                located.sort_unstable();
+                assert_eq!(located, naive);
+            }
+        }
+        assert_eq!(index.count(&[9, 9]), 0);
+        assert!(index.locate(&[9]).is_empty());
+        assert_eq!(index.count(&[0]), 0);
+        assert!(index.locate(&[1, 0]).is_empty());
+    }
+    assert!(RIndex::new(b"a\0b").is_err());
+}
+
+#[test]
+fn test_rindex_fibonacci() {
+    for k in 4..20 {
+        let text = crate::word::fibonacci_word(k);
+        let index = RIndex::new(&text).unwrap();
+        let bwt = {
+            let mut text = text.clone();
+            text.push(0u8);
+            crate::core::bwt_from_text_by_sa(&text).unwrap()
+        };
+        assert_eq!(
+            index.number_of_runs(),
+            crate::core::number_of_runs(&mut bwt.as_slice()).unwrap()
+        );
+        assert_eq!(
+            index.count(b"ab"),
+            text.windows(2).filter(|w| *w == b"ab").count()
+        );
+        assert_eq!(index.locate(b"abaab").len(), index.count(b"abaab"));
+    }
+}
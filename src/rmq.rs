@@ -0,0 +1,332 @@
+use more_asserts::{assert_le, debug_assert_lt};
+
+/// range minimum queries on an array
+pub trait RangeMinimumQuery<T: Ord + Copy> {
+    /// the array on which the queries are answered
+    fn array(&self) -> &[T];
+
+    /// the position of the leftmost minimum in arr[begin..end)
+    fn argmin(&self, begin: usize, end: usize) -> usize;
+
+    /// the minimum value in arr[begin..end)
+    fn query(&self, begin: usize, end: usize) -> T {
+        self.array()[self.argmin(begin, end)]
+    }
+
+    /// space of the data structure in bytes, excluding the array
+    fn size_in_bytes(&self) -> usize;
+}
+
+/// returns the position of the leftmost minimum among the positions `a` and `b`
+fn leftmost_minimum<T: Ord>(arr: &[T], a: usize, b: usize) -> usize {
+    if arr[b] < arr[a] || (arr[b] == arr[a] && b < a) {
+        b
+    } else {
+        a
+    }
+}
+
+/// the position of the leftmost minimum in arr[begin..end) found by a linear scan
+fn scan_argmin<T: Ord>(arr: &[T], begin: usize, end: usize) -> usize {
+    debug_assert_lt!(begin, end);
+    let mut best = begin;
+    for i in begin + 1..end {
+        if arr[i] < arr[best] {
+            best = i;
+        }
+    }
+    best
+}
+
+/// floor(lg(x)) for x > 0
+fn log2_floor(x: usize) -> usize {
+    (usize::BITS - 1 - x.leading_zeros()) as usize
+}
+
+/// Sparse table storing for each position i and each k the position of the minimum in
+/// arr[i..i+2^k). Takes O(n lg n) words and answers queries in constant time.
+/// Reference: Michael A. Bender, Martin Farach-Colton: "The LCA Problem Revisited". LATIN 2000: 88-94
+pub struct SparseTable<'a, T: Ord + Copy> {
+    arr: &'a [T],
+    /// table[k][i] is the position of the minimum in arr[i..i+2^(k+1))
+    table: Vec<Vec<u32>>,
+}
+
+impl<'a, T: Ord + Copy> SparseTable<'a, T> {
+    pub fn new(arr: &'a [T]) -> SparseTable<'a, T> {
+        assert_le!(arr.len(), u32::MAX as usize);
+        let mut table: Vec<Vec<u32>> = Vec::new();
+        let mut width = 1;
+        while 2 * width <= arr.len() {
+            let level: Vec<u32> = (0..arr.len() + 1 - 2 * width)
+                .map(|i| match table.last() {
+                    None => leftmost_minimum(arr, i, i + 1) as u32,
+                    Some(previous) => {
+                        leftmost_minimum(arr, previous[i] as usize, previous[i + width] as usize)
+                            as u32
+                    }
+                })
+                .collect();
+            table.push(level);
+            width *= 2;
+        }
+        SparseTable { arr, table }
+    }
+}
+
+impl<T: Ord + Copy> RangeMinimumQuery<T> for SparseTable<'_, T> {
+    fn array(&self) -> &[T] {
+        self.arr
+    }
+
+    fn argmin(&self, begin: usize, end: usize) -> usize {
+        debug_assert_lt!(begin, end);
+        debug_assert!(end <= self.arr.len());
+        let length = end - begin;
+        if length == 1 {
+            return begin;
+        }
+        let k = log2_floor(length);
+        let level = &self.table[k - 1];
+        leftmost_minimum(
+            self.arr,
+            level[begin] as usize,
+            level[end - (1 << k)] as usize,
+        )
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.table
+            .iter()
+            .map(|level| level.len() * std::mem::size_of::<u32>())
+            .sum()
+    }
+}
+
+/// Three-level range minimum query data structure in the spirit of Fischer and Heun, using
+/// O(n) bits in addition to the array.
+/// The array is partitioned into superblocks of `SUPERBLOCK_SIZE` elements, which are partitioned
+/// into blocks of `BLOCK_SIZE` elements. A sparse table on the superblock minima answers queries
+/// spanning full superblocks, a sparse table on the block minima of each superblock with 16-bit
+/// relative positions answers queries spanning full blocks, and the remaining queries inside a
+/// block are answered by scanning at most `BLOCK_SIZE` elements.
+/// Reference: Johannes Fischer, Volker Heun: "Space-Efficient Preprocessing Schemes for Range
+/// Minimum Queries on Static Arrays". SIAM J. Comput. 40(2): 465-492 (2011)
+pub struct SuccinctRMQ<'a, T: Ord + Copy> {
+    arr: &'a [T],
+    /// superblock_table[k][s] is the position of the minimum in the superblocks s..s+2^(k+1)
+    superblock_table: Vec<Vec<u32>>,
+    /// the positions of the superblock minima
+    superblock_minima: Vec<u32>,
+    /// block_table[k][b] is the position relative to the superblock start of the minimum in the
+    /// blocks b..b+2^k, where b+2^k does not exceed the superblock of b
+    block_table: Vec<Vec<u16>>,
+}
+
+impl<'a, T: Ord + Copy> SuccinctRMQ<'a, T> {
+    const BLOCK_SIZE: usize = 64;
+    const SUPERBLOCK_SIZE: usize = 4096;
+    const BLOCKS_PER_SUPERBLOCK: usize = Self::SUPERBLOCK_SIZE / Self::BLOCK_SIZE;
+
+    pub fn new(arr: &'a [T]) -> SuccinctRMQ<'a, T> {
+        assert_le!(arr.len(), u32::MAX as usize);
+        let n = arr.len();
+        let blocks = n.div_ceil(Self::BLOCK_SIZE);
+        let superblocks = n.div_ceil(Self::SUPERBLOCK_SIZE);
+
+        let block_minimum = |b: usize| {
+            scan_argmin(
+                arr,
+                b * Self::BLOCK_SIZE,
+                std::cmp::min(n, (b + 1) * Self::BLOCK_SIZE),
+            )
+        };
+
+        let mut block_table: Vec<Vec<u16>> = Vec::new();
+        let mut width = 1;
+        while width <= Self::BLOCKS_PER_SUPERBLOCK {
+            let level: Vec<u16> = (0..blocks)
+                .map(|b| {
+                    let superblock_start =
+                        (b / Self::BLOCKS_PER_SUPERBLOCK) * Self::SUPERBLOCK_SIZE;
+                    let superblock_end_block =
+                        (b / Self::BLOCKS_PER_SUPERBLOCK + 1) * Self::BLOCKS_PER_SUPERBLOCK;
+                    let position = match block_table.last() {
+                        None => block_minimum(b),
+                        Some(previous) => {
+                            let half = width / 2;
+                            let left = superblock_start + previous[b] as usize;
+                            if b + half >= std::cmp::min(blocks, superblock_end_block) {
+                                left
+                            } else {
+                                leftmost_minimum(
+                                    arr,
+                                    left,
+                                    superblock_start + previous[b + half] as usize,
+                                )
+                            }
+                        }
+                    };
+                    (position - superblock_start) as u16
+                })
+                .collect();
+            block_table.push(level);
+            width *= 2;
+        }
+
+        let superblock_minima: Vec<u32> = (0..superblocks)
+            .map(|s| {
+                let b = s * Self::BLOCKS_PER_SUPERBLOCK;
+                (s * Self::SUPERBLOCK_SIZE + block_table.last().unwrap()[b] as usize) as u32
+            })
+            .collect();
+        let superblock_minima_values: Vec<T> = superblock_minima
+            .iter()
+            .map(|&position| arr[position as usize])
+            .collect();
+        let superblock_table = SparseTable::new(&superblock_minima_values)
+            .table
+            .into_iter()
+            .map(|level| {
+                level
+                    .into_iter()
+                    .map(|s| superblock_minima[s as usize])
+                    .collect()
+            })
+            .collect();
+
+        SuccinctRMQ {
+            arr,
+            superblock_table,
+            superblock_minima,
+            block_table,
+        }
+    }
+
+    /// the position of the minimum in the full blocks [begin_block, end_block) that lie in the
+    /// same superblock
+    fn block_argmin(&self, begin_block: usize, end_block: usize) -> usize {
+        debug_assert_lt!(begin_block, end_block);
+        let superblock_start = (begin_block / Self::BLOCKS_PER_SUPERBLOCK) * Self::SUPERBLOCK_SIZE;
+        let k = log2_floor(end_block - begin_block);
+        let level = &self.block_table[k];
+        leftmost_minimum(
+            self.arr,
+            superblock_start + level[begin_block] as usize,
+            superblock_start + level[end_block - (1 << k)] as usize,
+        )
+    }
+
+    /// the position of the minimum in the full superblocks [begin, end)
+    fn superblock_argmin(&self, begin: usize, end: usize) -> usize {
+        debug_assert_lt!(begin, end);
+        let length = end - begin;
+        if length == 1 {
+            return self.superblock_minima[begin] as usize;
+        }
+        let k = log2_floor(length);
+        let level = &self.superblock_table[k - 1];
+        leftmost_minimum(
+            self.arr,
+            level[begin] as usize,
+            level[end - (1 << k)] as usize,
+        )
+    }
+}
+
+impl<T: Ord + Copy> RangeMinimumQuery<T> for SuccinctRMQ<'_, T> {
+    fn array(&self) -> &[T] {
+        self.arr
+    }
+
+    fn argmin(&self, begin: usize, end: usize) -> usize {
+        debug_assert_lt!(begin, end);
+        debug_assert!(end <= self.arr.len());
+        let begin_block = begin / Self::BLOCK_SIZE;
+        let last_block = (end - 1) / Self::BLOCK_SIZE;
+        if begin_block == last_block {
+            return scan_argmin(self.arr, begin, end);
+        }
+        //@ the partial blocks at both ends
+        let mut best = scan_argmin(self.arr, begin, (begin_block + 1) * Self::BLOCK_SIZE);
+        let right = scan_argmin(self.arr, last_block * Self::BLOCK_SIZE, end);
+
+        //@ the full blocks in between
+        let (first_full, end_full) = (begin_block + 1, last_block);
+        if first_full < end_full {
+            let begin_superblock = first_full / Self::BLOCKS_PER_SUPERBLOCK;
+            let last_superblock = (end_full - 1) / Self::BLOCKS_PER_SUPERBLOCK;
+            if begin_superblock == last_superblock {
+                best = leftmost_minimum(self.arr, best, self.block_argmin(first_full, end_full));
+            } else {
+                let first_boundary = (begin_superblock + 1) * Self::BLOCKS_PER_SUPERBLOCK;
+                best = leftmost_minimum(
+                    self.arr,
+                    best,
+                    self.block_argmin(first_full, first_boundary),
+                );
+                if begin_superblock + 1 < last_superblock {
+                    best = leftmost_minimum(
+                        self.arr,
+                        best,
+                        self.superblock_argmin(begin_superblock + 1, last_superblock),
+                    );
+                }
+                let last_boundary = last_superblock * Self::BLOCKS_PER_SUPERBLOCK;
+                best = leftmost_minimum(self.arr, best, self.block_argmin(last_boundary, end_full));
+            }
+        }
+        leftmost_minimum(self.arr, best, right)
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        self.superblock_table
+            .iter()
+            .map(|level| level.len() * std::mem::size_of::<u32>())
+            .sum::<usize>()
+            + self.superblock_minima.len() * std::mem::size_of::<u32>()
+            + self
+                .block_table
+                .iter()
+                .map(|level| level.len() * std::mem::size_of::<u16>())
+                .sum::<usize>()
+    }
+}
+
+#[cfg(test)]
+fn check_rmq<R: RangeMinimumQuery<u32>>(arr: &[u32], rmq: &R, step: usize) {
+    for begin in (0..arr.len()).step_by(step) {
+        for end in (begin + 1..=arr.len()).step_by(step) {
+            let naive = scan_argmin(arr, begin, end);
+            assert_eq!(rmq.argmin(begin, end), naive);
+            assert_eq!(rmq.query(begin, end), arr[naive]);
+        }
+    }
+}
+
+#[test]
+fn test_sparse_table() {
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    for n in 1..100 {
+        let arr: Vec<u32> = (0..n).map(|_| rng.gen_range(0..8)).collect();
+        check_rmq(&arr, &SparseTable::new(&arr), 1);
+    }
+}
+
+#[test]
+fn test_succinct_rmq() {
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    for n in 1..200 {
+        let arr: Vec<u32> = (0..n).map(|_| rng.gen_range(0..8)).collect();
+        check_rmq(&arr, &SuccinctRMQ::new(&arr), 1);
+    }
+    //@ arrays spanning several superblocks
+    for n in [4096, 4097, 20000] {
+        let arr: Vec<u32> = (0..n).map(|_| rng.gen_range(0..1000)).collect();
+        check_rmq(&arr, &SuccinctRMQ::new(&arr), 61);
+        let decreasing: Vec<u32> = (0..n as u32).rev().collect();
+        check_rmq(&decreasing, &SuccinctRMQ::new(&decreasing), 97);
+    }
+}
@@ -0,0 +1,395 @@
+//! Exhaustive search for the strings of a given length and alphabet size that are extremal under
+//! one or two measures, running in parallel.
+use crate::error::{Error, Result};
+use crate::measure::Measure;
+use crate::necklace::{self, NecklaceKind};
+use itertools::Itertools;
+use rayon::prelude::*;
+
+/// the number of strings a thread evaluates at once
+const CHUNK_SIZE: usize = 1 << 12;
+
+/// The smallest and the largest value of a function on the searched strings, each with the
+/// lexicographically smallest string attaining it as witness.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Extremes<T> {
+    pub min: T,
+    pub min_witness: Vec<u8>,
+    pub max: T,
+    pub max_witness: Vec<u8>,
+}
+
+impl<T: PartialOrd + Copy> Extremes<T> {
+    fn new(value: T, witness: &[u8]) -> Extremes<T> {
+        Extremes {
+            min: value,
+            min_witness: witness.to_vec(),
+            max: value,
+            max_witness: witness.to_vec(),
+        }
+    }
+
+    /// the extremes of the union of the strings of both, independent of the order of merging
+    fn merge(mut self, other: Extremes<T>) -> Extremes<T> {
+        if other.min < self.min || (other.min == self.min && other.min_witness < self.min_witness) {
+            self.min = other.min;
+            self.min_witness = other.min_witness;
+        }
+        if other.max > self.max || (other.max == self.max && other.max_witness < self.max_witness) {
+            self.max = other.max;
+            self.max_witness = other.max_witness;
+        }
+        self
+    }
+}
+
+fn merge_extremes<T: PartialOrd + Copy>(
+    a: Option<Extremes<T>>,
+    b: Option<Extremes<T>>,
+) -> Option<Extremes<T>> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.merge(b)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// the outcome of a search
+#[derive(Clone, Debug, Default)]
+pub struct Summary {
+    /// the number of evaluated strings
+    pub strings: usize,
+    /// the extremes of each measure
+    pub measures: Vec<Option<Extremes<usize>>>,
+    /// the extremes of the ratio of the first to the second measure, among the strings for which
+    /// the second measure is not zero
+    pub ratio: Option<Extremes<f64>>,
+}
+
+impl Summary {
+    fn empty(measures: usize) -> Summary {
+        Summary {
+            strings: 0,
+            measures: vec![None; measures],
+            ratio: None,
+        }
+    }
+
+    fn of(text: &[u8], measures: &[Measure]) -> Result<Summary> {
+        let values = measures
+            .iter()
+            .map(|measure| measure.evaluate(text))
+            .collect::<Result<Vec<usize>>>()?;
+        let ratio = match values.as_slice() {
+            [a, b] if *b > 0 => Some(Extremes::new(*a as f64 / *b as f64, text)),
+            _ => None,
+        };
+        Ok(Summary {
+            strings: 1,
+            measures: values
+                .into_iter()
+                .map(|value| Some(Extremes::new(value, text)))
+                .collect(),
+            ratio,
+        })
+    }
+
+    fn merge(self, other: Summary) -> Summary {
+        Summary {
+            strings: self.strings + other.strings,
+            measures: self
+                .measures
+                .into_iter()
+                .zip(other.measures)
+                .map(|(a, b)| merge_extremes(a, b))
+                .collect(),
+            ratio: merge_extremes(self.ratio, other.ratio),
+        }
+    }
+}
+
+/// The strings to search: all strings, or those of a kind like the necklaces, of a given length
+/// over the letters a, b, ... of an alphabet of size sigma.
+#[derive(Clone, Debug)]
+pub struct SearchSpace {
+    pub length: usize,
+    pub sigma: usize,
+    /// the kind of strings, or None for all strings
+    pub kind: Option<NecklaceKind>,
+    /// whether to evaluate only the smallest string of each class of strings equal under renaming
+    /// the letters, which is only sound for measures invariant under renaming
+    pub canonical: bool,
+}
+
+impl SearchSpace {
+    /// the strings over the alphabet {0, ..., sigma-1} in lexicographic order
+    fn words(&self) -> Result<Box<dyn Iterator<Item = Vec<u8>> + Send>> {
+        Ok(match self.kind {
+            Some(kind) => Box::new(necklace::NecklaceGenerator::new(
+                self.length,
+                self.sigma,
+                kind,
+            )?),
+            //@ the product of no ranges is empty instead of containing the empty word
+            None if self.length == 0 => Box::new(std::iter::once(Vec::new())),
+            None => Box::new(
+                (0..self.length)
+                    .map(|_| 0..self.sigma as u8)
+                    .multi_cartesian_product(),
+            ),
+        })
+    }
+
+    /// the string representing the class of `word` in the search space, to check the canonical
+    /// strings against all renamings
+    #[cfg(test)]
+    fn representative(&self, word: &[u8]) -> Vec<u8> {
+        match self.kind {
+            None => word.to_vec(),
+            Some(NecklaceKind::Bracelet) => {
+                let mut reversal = word.to_vec();
+                reversal.reverse();
+                std::cmp::min(
+                    necklace::least_rotation(word),
+                    necklace::least_rotation(&reversal),
+                )
+            }
+            Some(_) => necklace::least_rotation(word),
+        }
+    }
+
+    /// Whether `word` is the smallest string of the search space among the renamings of its letters.
+    /// The representative of a renaming is the smallest of its rotations (and of the rotations of its
+    /// reversal for bracelets), such that the smallest representative of all renamings is the
+    /// smallest renaming of one of these rotations, instead of trying all sigma! renamings.
+    fn is_canonical(&self, word: &[u8]) -> bool {
+        match self.kind {
+            None => smallest_renaming(word.iter().copied()) == word,
+            Some(kind) => {
+                let mut reversal = word.to_vec();
+                reversal.reverse();
+                let conjugates: &[&[u8]] = if kind == NecklaceKind::Bracelet {
+                    &[word, &reversal]
+                } else {
+                    &[word]
+                };
+                conjugates.iter().all(|conjugate| {
+                    (0..conjugate.len()).all(|i| {
+                        let rotation = conjugate[i..].iter().chain(&conjugate[..i]).copied();
+                        smallest_renaming(rotation).as_slice() >= word
+                    })
+                })
+            }
+        }
+    }
+
+    /// checks that the search space is well-defined
+    fn check(&self) -> Result<()> {
+        if !(1..=26).contains(&self.sigma) {
+            return Err(Error::InvalidArgument(format!(
+                "the alphabet size {} is not in 1..=26",
+                self.sigma
+            )));
+        }
+        if self.canonical && self.kind == Some(NecklaceKind::Prenecklace) {
+            return Err(Error::InvalidArgument(String::from(
+                "the renamings of a prenecklace are not represented by prenecklaces",
+            )));
+        }
+        Ok(())
+    }
+
+    /// checks that `measure` does not change under renaming the letters if only the canonical
+    /// strings are evaluated
+    pub fn check_measure(&self, measure: Measure) -> Result<()> {
+        if self.canonical && !measure.is_renaming_invariant() {
+            return Err(Error::InvalidArgument(format!(
+                "the measure {} is not invariant under renaming the letters, such that the canonical strings do not suffice",
+                measure.name()
+            )));
+        }
+        Ok(())
+    }
+
+    /// Maps each string of the search space, written with the letters a, b, ..., by `map`, and
+    /// merges the results by `merge` starting with `empty`. The strings are enumerated
+    /// sequentially, and mapped in parallel in chunks by the current rayon thread pool.
+    pub fn map_reduce<S, E, M, R>(&self, empty: E, map: M, merge: R) -> Result<S>
+    where
+        S: Send,
+        E: Fn() -> S + Sync + Send,
+        M: Fn(&[u8]) -> Result<S> + Sync + Send,
+        R: Fn(S, S) -> S + Sync + Send,
+    {
+        self.check()?;
+        let mut words = self.words()?.fuse(); //@ MultiProduct restarts after its end
+        let chunks = std::iter::from_fn(move || {
+            let chunk: Vec<Vec<u8>> = words.by_ref().take(CHUNK_SIZE).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        });
+        chunks
+            .par_bridge()
+            .map(|chunk| {
+                let mut result = empty();
+                for word in chunk {
+                    if self.canonical && !self.is_canonical(&word) {
+                        continue;
+                    }
+                    let text: Vec<u8> = word.iter().map(|&c| b'a' + c).collect();
+                    result = merge(result, map(&text)?);
+                }
+                Ok(result)
+            })
+            .try_reduce(&empty, |a, b| Ok(merge(a, b)))
+    }
+}
+
+/// the lexicographically smallest renaming of `word`, which renames its letters in the order of
+/// their first occurrences to 0, 1, ..., i.e., the restricted growth string of `word`
+fn smallest_renaming(word: impl Iterator<Item = u8>) -> Vec<u8> {
+    let mut renaming = [None; 256];
+    let mut letters = 0;
+    word.map(|c| {
+        *renaming[c as usize].get_or_insert_with(|| {
+            letters += 1;
+            letters - 1
+        })
+    })
+    .collect()
+}
+
+/// Evaluates one or two measures on all strings of the search space in parallel, and returns
+/// their extremes and the extremes of their ratio if two measures are given.
+pub fn search(space: &SearchSpace, measures: &[Measure]) -> Result<Summary> {
+    if !(1..=2).contains(&measures.len()) {
+        return Err(Error::InvalidArgument(String::from(
+            "the search takes one or two measures",
+        )));
+    }
+    for &measure in measures {
+        space.check_measure(measure)?;
+    }
+    space.map_reduce(
+        || Summary::empty(measures.len()),
+        |text| Summary::of(text, measures),
+        Summary::merge,
+    )
+}
+
+#[test]
+fn test_search() {
+    let space = |length, sigma, kind, canonical| SearchSpace {
+        length,
+        sigma,
+        kind,
+        canonical,
+    };
+    let summary = search(&space(6, 2, None, false), &[Measure::LyndonFactors]).unwrap();
+    assert_eq!(summary.strings, 64);
+    let lyndon = summary.measures[0].as_ref().unwrap();
+    assert_eq!(
+        (lyndon.min, lyndon.min_witness.as_slice()),
+        (1, &b"aaaaab"[..])
+    );
+    assert_eq!(
+        (lyndon.max, lyndon.max_witness.as_slice()),
+        (6, &b"aaaaaa"[..])
+    );
+    assert!(summary.ratio.is_none());
+
+    //@ the witnesses are the lexicographically smallest, independent of the parallel evaluation
+    let measures = [Measure::R, Measure::Z];
+    let summary = search(&space(10, 2, None, false), &measures).unwrap();
+    assert_eq!(summary.strings, 1 << 10);
+    let mut texts: Vec<Vec<u8>> =
+        itertools::Itertools::multi_cartesian_product((0..10).map(|_| b'a'..=b'b')).collect();
+    texts.sort();
+    for (i, measure) in measures.iter().enumerate() {
+        let values: Vec<usize> = texts.iter().map(|t| measure.evaluate(t).unwrap()).collect();
+        let extremes = summary.measures[i].as_ref().unwrap();
+        let min = *values.iter().min().unwrap();
+        let max = *values.iter().max().unwrap();
+        assert_eq!(extremes.min, min);
+        assert_eq!(extremes.max, max);
+        assert_eq!(
+            extremes.min_witness,
+            texts[values.iter().position(|&v| v == min).unwrap()]
+        );
+        assert_eq!(
+            extremes.max_witness,
+            texts[values.iter().position(|&v| v == max).unwrap()]
+        );
+    }
+    let ratio = summary.ratio.unwrap();
+    assert_eq!(
+        ratio.max,
+        texts
+            .iter()
+            .map(|t| Measure::R.evaluate(t).unwrap() as f64 / Measure::Z.evaluate(t).unwrap() as f64)
+            .fold(0.0, f64::max)
+    );
+
+    //@ the necklaces of length 6 are the canonical strings under rotation, and z is invariant under renaming
+    assert_eq!(
+        search(
+            &space(6, 2, Some(NecklaceKind::Necklace), false),
+            &[Measure::Z]
+        )
+        .unwrap()
+        .strings,
+        14
+    );
+    let all = search(&space(8, 3, None, false), &[Measure::Z]).unwrap();
+    let canonical = search(&space(8, 3, None, true), &[Measure::Z]).unwrap();
+    assert_eq!(all.measures, canonical.measures);
+    //@ the restricted growth strings of length 8 with at most 3 letters: sum of Stirling numbers S(8, k) for k <= 3
+    assert_eq!(canonical.strings, 1 + 127 + 966);
+    let necklaces = search(
+        &space(8, 3, Some(NecklaceKind::Necklace), true),
+        &[Measure::Z],
+    )
+    .unwrap();
+    assert!(necklaces.strings < canonical.strings);
+
+    //@ the renamings of the first occurrences agree with trying all renamings
+    for kind in [
+        None,
+        Some(NecklaceKind::Necklace),
+        Some(NecklaceKind::Lyndon),
+        Some(NecklaceKind::Bracelet),
+    ] {
+        let space = space(6, 3, kind, true);
+        for word in space.words().unwrap() {
+            let naive = (0..3).permutations(3).all(|renaming| {
+                let renamed: Vec<u8> = word.iter().map(|&c| renaming[c as usize]).collect();
+                space.representative(&renamed) >= word
+            });
+            assert_eq!(space.is_canonical(&word), naive, "{:?} {:?}", kind, word);
+        }
+    }
+    //@ the empty string is the only string of length 0, but not a Lyndon word
+    assert_eq!(
+        search(&space(0, 2, None, true), &[Measure::Z])
+            .unwrap()
+            .strings,
+        1
+    );
+    assert_eq!(
+        search(
+            &space(0, 2, Some(NecklaceKind::Lyndon), false),
+            &[Measure::Z]
+        )
+        .unwrap()
+        .strings,
+        0
+    );
+
+    assert!(search(&space(4, 2, None, false), &[]).is_err());
+    assert!(search(&space(4, 2, None, true), &[Measure::Z, Measure::R]).is_err());
+    assert!(search(&space(4, 27, None, false), &[Measure::Z]).is_err());
+    assert!(search(
+        &space(4, 2, Some(NecklaceKind::Prenecklace), true),
+        &[Measure::Z]
+    )
+    .is_err());
+}
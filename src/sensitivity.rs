@@ -0,0 +1,337 @@
+//! The sensitivity of a measure to single edits: how much the measure of a string, or of all
+//! strings of a search space, can grow or shrink by substituting, inserting or deleting a letter,
+//! or by rotating the string.
+use crate::error::Result;
+use crate::measure::Measure;
+use crate::search::SearchSpace;
+use rayon::prelude::*;
+
+/// a kind of edit operations
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EditKind {
+    /// replace a letter by another letter
+    Substitution,
+    /// insert a letter at a position
+    Insertion,
+    /// delete the letter at a position
+    Deletion,
+    /// move a non-empty proper prefix to the end
+    Rotation,
+}
+
+impl EditKind {
+    /// the name of the kind as given on the command line
+    pub fn name(&self) -> &'static str {
+        match self {
+            EditKind::Substitution => "substitution",
+            EditKind::Insertion => "insertion",
+            EditKind::Deletion => "deletion",
+            EditKind::Rotation => "rotation",
+        }
+    }
+}
+
+/// a single edit at a position (starting with 0) of a string
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Edit {
+    /// replaces the letter at the position by the given letter
+    Substitution(usize, u8),
+    /// inserts the given letter before the letter at the position, or at the end
+    Insertion(usize, u8),
+    /// deletes the letter at the position
+    Deletion(usize),
+    /// moves the prefix of the given length to the end
+    Rotation(usize),
+}
+
+impl Edit {
+    pub fn kind(&self) -> EditKind {
+        match self {
+            Edit::Substitution(..) => EditKind::Substitution,
+            Edit::Insertion(..) => EditKind::Insertion,
+            Edit::Deletion(_) => EditKind::Deletion,
+            Edit::Rotation(_) => EditKind::Rotation,
+        }
+    }
+
+    /// the edited copy of `text`
+    pub fn apply(&self, text: &[u8]) -> Vec<u8> {
+        let mut edited = text.to_vec();
+        match *self {
+            Edit::Substitution(i, c) => edited[i] = c,
+            Edit::Insertion(i, c) => edited.insert(i, c),
+            Edit::Deletion(i) => {
+                edited.remove(i);
+            }
+            Edit::Rotation(i) => edited.rotate_left(i),
+        }
+        edited
+    }
+}
+
+impl std::fmt::Display for Edit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Edit::Substitution(i, c) => write!(f, "sub({},{})", i, c as char),
+            Edit::Insertion(i, c) => write!(f, "ins({},{})", i, c as char),
+            Edit::Deletion(i) => write!(f, "del({})", i),
+            Edit::Rotation(i) => write!(f, "rot({})", i),
+        }
+    }
+}
+
+/// All edits of `kind` of `text`, where substitutions and insertions use the letters of `alphabet`,
+/// and substitutions change the letter.
+pub fn edits(text: &[u8], alphabet: &[u8], kind: EditKind) -> Vec<Edit> {
+    let n = text.len();
+    match kind {
+        EditKind::Substitution => (0..n)
+            .flat_map(|i| {
+                alphabet
+                    .iter()
+                    .filter(move |&&c| c != text[i])
+                    .map(move |&c| Edit::Substitution(i, c))
+            })
+            .collect(),
+        EditKind::Insertion => (0..=n)
+            .flat_map(|i| alphabet.iter().map(move |&c| Edit::Insertion(i, c)))
+            .collect(),
+        EditKind::Deletion => (0..n).map(Edit::Deletion).collect(),
+        EditKind::Rotation => (1..n).map(Edit::Rotation).collect(),
+    }
+}
+
+/// an edit of a string with the values of a measure before and after the edit
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub text: Vec<u8>,
+    pub edit: Edit,
+    pub before: usize,
+    pub after: usize,
+}
+
+impl Change {
+    /// the value after the edit divided by the value before, or None if the value before is 0
+    pub fn ratio(&self) -> Option<f64> {
+        (self.before > 0).then(|| self.after as f64 / self.before as f64)
+    }
+
+    pub fn increase(&self) -> usize {
+        self.after.saturating_sub(self.before)
+    }
+
+    pub fn decrease(&self) -> usize {
+        self.before.saturating_sub(self.after)
+    }
+}
+
+/// The change of `a` and `b` with the larger key, or on a tie, the one with the lexicographically
+/// smaller string and edit, such that the choice is independent of the order of merging.
+fn pick<K: PartialOrd>(
+    a: Option<Change>,
+    b: Option<Change>,
+    key: impl Fn(&Change) -> K,
+) -> Option<Change> {
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let (key_a, key_b) = (key(&a), key(&b));
+            if key_b > key_a || (key_b == key_a && (&b.text, b.edit) < (&a.text, a.edit)) {
+                Some(b)
+            } else {
+                Some(a)
+            }
+        }
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
+
+/// the largest changes of a measure under edits, each with a witness
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Sensitivity {
+    /// the number of edited strings
+    pub strings: usize,
+    /// the number of evaluated edits
+    pub edits: usize,
+    /// the edit with the largest ratio of the values after and before
+    pub max_ratio: Option<Change>,
+    /// the edit with the smallest ratio of the values after and before
+    pub min_ratio: Option<Change>,
+    /// the edit increasing the value the most, if any edit increases it
+    pub max_increase: Option<Change>,
+    /// the edit decreasing the value the most, if any edit decreases it
+    pub max_decrease: Option<Change>,
+}
+
+impl Sensitivity {
+    fn of(change: Change) -> Sensitivity {
+        let ratio = change.ratio().map(|_| change.clone());
+        Sensitivity {
+            strings: 0,
+            edits: 1,
+            max_ratio: ratio.clone(),
+            min_ratio: ratio,
+            max_increase: (change.after > change.before).then(|| change.clone()),
+            max_decrease: (change.after < change.before).then_some(change),
+        }
+    }
+
+    fn merge(self, other: Sensitivity) -> Sensitivity {
+        Sensitivity {
+            strings: self.strings + other.strings,
+            edits: self.edits + other.edits,
+            max_ratio: pick(self.max_ratio, other.max_ratio, Change::ratio),
+            min_ratio: pick(self.min_ratio, other.min_ratio, |c| c.ratio().map(|r| -r)),
+            max_increase: pick(self.max_increase, other.max_increase, Change::increase),
+            max_decrease: pick(self.max_decrease, other.max_decrease, Change::decrease),
+        }
+    }
+}
+
+/// the change of `measure` of `text` with value `before` under `edit`
+fn change(text: &[u8], edit: Edit, before: usize, measure: Measure) -> Result<Sensitivity> {
+    Ok(Sensitivity::of(Change {
+        text: text.to_vec(),
+        edit,
+        before,
+        after: measure.evaluate(&edit.apply(text))?,
+    }))
+}
+
+/// Evaluates `measure` on `text` after each edit of `kind`, in parallel by the current rayon
+/// thread pool, where substitutions and insertions use the letters of `alphabet`.
+pub fn sensitivity(
+    text: &[u8],
+    alphabet: &[u8],
+    kind: EditKind,
+    measure: Measure,
+) -> Result<Sensitivity> {
+    let before = measure.evaluate(text)?;
+    let sensitivity = edits(text, alphabet, kind)
+        .into_par_iter()
+        .map(|edit| change(text, edit, before, measure))
+        .try_reduce(Sensitivity::default, |a, b| Ok(a.merge(b)))?;
+    Ok(Sensitivity {
+        strings: 1,
+        ..sensitivity
+    })
+}
+
+/// Evaluates `measure` on all strings of `space` after each of their edits of `kind`, where
+/// substitutions and insertions use the letters of the alphabet of the search space.
+pub fn exhaustive_sensitivity(
+    space: &SearchSpace,
+    kind: EditKind,
+    measure: Measure,
+) -> Result<Sensitivity> {
+    space.check_measure(measure)?;
+    let alphabet: Vec<u8> = (b'a'..).take(space.sigma).collect();
+    space.map_reduce(
+        Sensitivity::default,
+        |text| {
+            let before = measure.evaluate(text)?;
+            let mut sensitivity = Sensitivity {
+                strings: 1,
+                ..Sensitivity::default()
+            };
+            for edit in edits(text, &alphabet, kind) {
+                sensitivity = sensitivity.merge(change(text, edit, before, measure)?);
+            }
+            Ok(sensitivity)
+        },
+        Sensitivity::merge,
+    )
+}
+
+#[test]
+fn test_edits() {
+    let text = b"abc";
+    let substitutions = edits(text, b"ab", EditKind::Substitution);
+    assert_eq!(
+        substitutions,
+        [
+            Edit::Substitution(0, b'b'),
+            Edit::Substitution(1, b'a'),
+            Edit::Substitution(2, b'a'),
+            Edit::Substitution(2, b'b')
+        ]
+    );
+    assert_eq!(edits(text, b"ab", EditKind::Insertion).len(), 8);
+    assert_eq!(edits(text, b"ab", EditKind::Deletion).len(), 3);
+    assert_eq!(edits(text, b"ab", EditKind::Rotation).len(), 2);
+    assert_eq!(Edit::Substitution(1, b'c').apply(text), b"acc");
+    assert_eq!(Edit::Insertion(3, b'a').apply(text), b"abca");
+    assert_eq!(Edit::Deletion(0).apply(text), b"bc");
+    assert_eq!(Edit::Rotation(1).apply(text), b"bca");
+    assert_eq!(Edit::Rotation(1).to_string(), "rot(1)");
+    assert_eq!(Edit::Insertion(3, b'a').to_string(), "ins(3,a)");
+    assert!(edits(text, b"ab", EditKind::Rotation)
+        .iter()
+        .all(|edit| edit.kind() == EditKind::Rotation));
+}
+
+#[test]
+fn test_sensitivity() {
+    //@ deleting the b of aaaabaaaa merges the runs of a
+    let single = sensitivity(b"aaaabaaaa", b"ab", EditKind::Deletion, Measure::Z).unwrap();
+    assert_eq!(single.strings, 1);
+    assert_eq!(single.edits, 9);
+    let decrease = single.max_decrease.unwrap();
+    assert_eq!((decrease.before, decrease.after), (4, 2));
+    assert_eq!(decrease.edit, Edit::Deletion(4));
+    //@ a|aa|b|aaa|a after deleting the first a
+    let increase = single.max_increase.unwrap();
+    assert_eq!((increase.edit, increase.after), (Edit::Deletion(0), 5));
+    assert_eq!(single.min_ratio.unwrap().ratio(), Some(0.5));
+
+    //@ the exhaustive evaluation agrees with evaluating each string
+    let space = SearchSpace {
+        length: 6,
+        sigma: 2,
+        kind: None,
+        canonical: false,
+    };
+    for kind in [
+        EditKind::Substitution,
+        EditKind::Insertion,
+        EditKind::Deletion,
+        EditKind::Rotation,
+    ] {
+        let exhaustive = exhaustive_sensitivity(&space, kind, Measure::R).unwrap();
+        assert_eq!(exhaustive.strings, 64);
+        let each = itertools::Itertools::multi_cartesian_product((0..6).map(|_| b'a'..=b'b'))
+            .map(|text| sensitivity(&text, b"ab", kind, Measure::R).unwrap())
+            .fold(Sensitivity::default(), Sensitivity::merge);
+        assert_eq!(exhaustive, each);
+    }
+
+    //@ gamma and z are invariant under renaming, such that the canonical strings suffice
+    let canonical = SearchSpace {
+        length: 6,
+        sigma: 3,
+        kind: None,
+        canonical: true,
+    };
+    for measure in [Measure::Z, Measure::Gamma] {
+        let all = exhaustive_sensitivity(
+            &SearchSpace {
+                canonical: false,
+                ..canonical.clone()
+            },
+            EditKind::Substitution,
+            measure,
+        )
+        .unwrap();
+        let pruned = exhaustive_sensitivity(&canonical, EditKind::Substitution, measure).unwrap();
+        assert!(pruned.strings < all.strings);
+        assert_eq!(
+            all.max_ratio.unwrap().ratio(),
+            pruned.max_ratio.unwrap().ratio()
+        );
+        assert_eq!(
+            all.max_increase.unwrap().increase(),
+            pruned.max_increase.unwrap().increase()
+        );
+    }
+    assert!(exhaustive_sensitivity(&canonical, EditKind::Substitution, Measure::R).is_err());
+}
@@ -0,0 +1,315 @@
+use std::collections::BTreeMap;
+
+/// a state of the suffix automaton
+#[derive(Debug, Clone)]
+struct State<C: Ord + Copy> {
+    /// length of the longest string in the equivalence class of this state
+    len: usize,
+    /// suffix link, `None` for the initial state
+    link: Option<usize>,
+    /// outgoing transitions
+    next: BTreeMap<C, usize>,
+    /// whether this state was created for a new text position, i.e., is not a clone
+    is_primary: bool,
+}
+
+/// The suffix automaton (also called DAWG) of a text, built online character by character.
+/// Reference: Anselm Blumer, J. Blumer, David Haussler, Andrzej Ehrenfeucht, M. T. Chen, Joel I. Seiferas:
+/// "The Smallest Automaton Recognizing the Subwords of a Text". Theor. Comput. Sci. 40: 31-55 (1985)
+#[derive(Debug, Clone)]
+pub struct SuffixAutomaton<C: Ord + Copy> {
+    states: Vec<State<C>>,
+    /// the state representing the entire text read so far
+    last: usize,
+    /// number of transitions
+    transitions: usize,
+    /// number of distinct non-empty substrings of the text read so far
+    distinct_substrings: usize,
+    /// length of the text read so far
+    length: usize,
+}
+
+impl<C: Ord + Copy> Default for SuffixAutomaton<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Ord + Copy> SuffixAutomaton<C> {
+    /// creates the automaton of the empty string
+    pub fn new() -> SuffixAutomaton<C> {
+        SuffixAutomaton {
+            states: vec![State {
+                len: 0,
+                link: None,
+                next: BTreeMap::new(),
+                is_primary: false,
+            }],
+            last: 0,
+            transitions: 0,
+            distinct_substrings: 0,
+            length: 0,
+        }
+    }
+
+    /// builds the automaton of `text`
+    pub fn from_text(text: &[C]) -> SuffixAutomaton<C> {
+        let mut automaton = SuffixAutomaton::new();
+        for &c in text {
+            automaton.extend(c);
+        }
+        automaton
+    }
+
+    /// appends the character `c` to the text, in amortized O(lg σ) time
+    pub fn extend(&mut self, c: C) {
+        let current = self.states.len();
+        self.states.push(State {
+            len: self.states[self.last].len + 1,
+            link: None,
+            next: BTreeMap::new(),
+            is_primary: true,
+        });
+        let mut p = Some(self.last);
+        while let Some(state) = p {
+            if self.states[state].next.contains_key(&c) {
+                break;
+            }
+            self.states[state].next.insert(c, current);
+            self.transitions += 1;
+            p = self.states[state].link;
+        }
+        match p {
+            None => self.states[current].link = Some(0),
+            Some(p) => {
+                let q = self.states[p].next[&c];
+                if self.states[p].len + 1 == self.states[q].len {
+                    self.states[current].link = Some(q);
+                } else {
+                    //@ split q by cloning it
+                    let clone = self.states.len();
+                    let cloned_state = State {
+                        len: self.states[p].len + 1,
+                        link: self.states[q].link,
+                        next: self.states[q].next.clone(),
+                        is_primary: false,
+                    };
+                    self.transitions += cloned_state.next.len();
+                    self.states.push(cloned_state);
+                    let mut p = Some(p);
+                    while let Some(state) = p {
+                        match self.states[state].next.get_mut(&c) {
+                            Some(target) if *target == q => *target = clone,
+                            _ => break,
+                        }
+                        p = self.states[state].link;
+                    }
+                    self.states[q].link = Some(clone);
+                    self.states[current].link = Some(clone);
+                }
+            }
+        }
+        self.last = current;
+        self.length += 1;
+        //@ the new substrings are exactly the suffixes longer than the suffix link of the new state
+        let link = self.states[current].link.unwrap();
+        self.distinct_substrings += self.states[current].len - self.states[link].len;
+    }
+
+    /// length of the text read so far
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// number of states, including the initial state
+    pub fn number_of_states(&self) -> usize {
+        self.states.len()
+    }
+
+    /// number of transitions
+    pub fn number_of_transitions(&self) -> usize {
+        self.transitions
+    }
+
+    /// number of distinct non-empty substrings of the text read so far
+    pub fn number_of_distinct_substrings(&self) -> usize {
+        self.distinct_substrings
+    }
+
+    /// the state reached by reading `pattern` from the initial state, if `pattern` is a substring
+    fn find_state(&self, pattern: &[C]) -> Option<usize> {
+        let mut state = 0;
+        for c in pattern {
+            state = *self.states[state].next.get(c)?;
+        }
+        Some(state)
+    }
+
+    /// checks whether `pattern` is a substring of the text
+    pub fn contains(&self, pattern: &[C]) -> bool {
+        self.find_state(pattern).is_some()
+    }
+
+    /// computes for each state the number of ending positions of its strings in the text.
+    /// The result is used as input for `count_occurrences`, and has to be recomputed after
+    /// calling `extend`.
+    pub fn occurrence_counts(&self) -> Vec<usize> {
+        let mut counts: Vec<usize> = self
+            .states
+            .iter()
+            .map(|state| state.is_primary as usize)
+            .collect();
+        //@ process states in decreasing order of their lengths, sorted by a counting sort
+        let mut buckets = vec![0_usize; self.length + 2];
+        for state in self.states.iter() {
+            buckets[state.len + 1] += 1;
+        }
+        for i in 1..buckets.len() {
+            buckets[i] += buckets[i - 1];
+        }
+        let mut order = vec![0; self.states.len()];
+        for (i, state) in self.states.iter().enumerate() {
+            order[buckets[state.len]] = i;
+            buckets[state.len] += 1;
+        }
+        for &state in order.iter().rev() {
+            if let Some(link) = self.states[state].link {
+                counts[link] += counts[state];
+            }
+        }
+        counts
+    }
+
+    /// number of occurrences of `pattern` in the text, where `counts` has been computed by
+    /// `occurrence_counts`
+    pub fn count_occurrences(&self, counts: &[usize], pattern: &[C]) -> usize {
+        debug_assert_eq!(counts.len(), self.states.len());
+        if pattern.is_empty() {
+            return self.length + 1;
+        }
+        match self.find_state(pattern) {
+            None => 0,
+            Some(state) => counts[state],
+        }
+    }
+
+    /// the substring complexity d_k, i.e., the number of distinct substrings of length k,
+    /// for each k in [0..max_length]. The entry at index 0 is always 1 (the empty string).
+    pub fn substring_complexity(&self, max_length: usize) -> Vec<usize> {
+        //@ each state v contributes exactly one substring of each length in (len(link(v))..len(v)]
+        let mut difference = vec![0_isize; max_length + 2];
+        for state in self.states.iter().skip(1) {
+            let from = self.states[state.link.unwrap()].len + 1;
+            if from > max_length {
+                continue;
+            }
+            let to = std::cmp::min(state.len, max_length);
+            difference[from] += 1;
+            difference[to + 1] -= 1;
+        }
+        let mut profile = vec![0; max_length + 1];
+        profile[0] = 1;
+        let mut current = 0;
+        for k in 1..=max_length {
+            current += difference[k];
+            profile[k] = current as usize;
+        }
+        profile
+    }
+
+    /// computes the longest common substring of the text and `other`.
+    /// Returns the length of the longest common substring and its starting position in `other`.
+    pub fn longest_common_substring(&self, other: &[C]) -> (usize, usize) {
+        let mut state = 0;
+        let mut length = 0;
+        let mut best_length = 0;
+        let mut best_position = 0;
+        for (i, c) in other.iter().enumerate() {
+            while state != 0 && !self.states[state].next.contains_key(c) {
+                state = self.states[state].link.unwrap();
+                length = self.states[state].len;
+            }
+            if let Some(&target) = self.states[state].next.get(c) {
+                state = target;
+                length += 1;
+            }
+            if length > best_length {
+                best_length = length;
+                best_position = i + 1 - length;
+            }
+        }
+        (best_length, best_position)
+    }
+}
+
+#[cfg(test)]
+fn distinct_substrings_naive(text: &[u8]) -> std::collections::HashSet<&[u8]> {
+    let mut set = std::collections::HashSet::new();
+    for i in 0..text.len() {
+        for j in i + 1..=text.len() {
+            set.insert(&text[i..j]);
+        }
+    }
+    set
+}
+
+#[test]
+fn test_distinct_substrings() {
+    for text in crate::core::RandomStringGenerator::new(0..4096, 1) {
+        let automaton = SuffixAutomaton::from_text(&text);
+        let substrings = distinct_substrings_naive(&text);
+        assert_eq!(automaton.number_of_distinct_substrings(), substrings.len());
+        let profile = automaton.substring_complexity(text.len() + 1);
+        for (k, &d) in profile.iter().enumerate().skip(1) {
+            assert_eq!(d, substrings.iter().filter(|s| s.len() == k).count());
+        }
+        //@ the suffix automaton has at most 2n-1 states and 3n-4 transitions for n >= 3
+        if text.len() >= 3 {
+            assert!(automaton.number_of_states() < 2 * text.len());
+            assert!(automaton.number_of_transitions() <= 3 * text.len() - 4);
+        }
+    }
+}
+
+#[test]
+fn test_count_occurrences() {
+    let text = crate::word::fibonacci_word(8);
+    let automaton = SuffixAutomaton::from_text(&text);
+    let counts = automaton.occurrence_counts();
+    for i in 0..text.len() {
+        for j in i + 1..std::cmp::min(text.len(), i + 8) {
+            let pattern = &text[i..j];
+            let naive = text
+                .windows(pattern.len())
+                .filter(|w| *w == pattern)
+                .count();
+            assert_eq!(automaton.count_occurrences(&counts, pattern), naive);
+        }
+    }
+    assert_eq!(automaton.count_occurrences(&counts, b"bb"), 0);
+    assert!(!automaton.contains(b"aaa"));
+}
+
+#[test]
+fn test_longest_common_substring() {
+    let automaton = SuffixAutomaton::from_text(b"xabcdey");
+    assert_eq!(automaton.longest_common_substring(b"zzbcdzz"), (3, 2));
+    assert_eq!(automaton.longest_common_substring(b"qqq"), (0, 0));
+    let automaton = SuffixAutomaton::from_text(b"abaababaab");
+    assert_eq!(automaton.longest_common_substring(b"babaa").0, 5);
+}
+
+/// the Fibonacci words have substring complexity d_k = k+1 as they are Sturmian
+#[test]
+fn test_substring_complexity_fibonacci() {
+    let text = crate::word::fibonacci_word(14);
+    let automaton = SuffixAutomaton::from_text(&text);
+    let profile = automaton.substring_complexity(100);
+    for (k, &d) in profile.iter().enumerate() {
+        assert_eq!(d, k + 1);
+    }
+}
@@ -1,4 +1,10 @@
-#[allow(dead_code)]
+#![allow(dead_code)]
+//@ the tests predating the clippy gate are kept as written
+#![allow(
+    clippy::unnecessary_cast,
+    clippy::into_iter_on_ref,
+    clippy::needless_borrow
+)]
 extern crate env_logger;
 #[macro_use]
 extern crate more_asserts;
@@ -30,17 +36,64 @@ fn test_duval() {
     }
 }
 
+#[test]
+fn test_lyndon_factor_counts_of_prefixes() {
+    for log_sigma in 1..3 {
+        for text in core::RandomStringGenerator::new(0..512, log_sigma) {
+            //@ drop the zero sentinel such that prefixes of periodic texts are covered
+            let text = &text[..text.len() - 1];
+            let lengths: Vec<usize> = (0..=text.len()).collect();
+            let counts = core::lyndon_factor_counts_of_prefixes(text, &lengths);
+            for (&length, count) in lengths.iter().zip(counts) {
+                assert_eq!(count, core::duval(&text[..length]).len());
+            }
+            let sparse = core::lyndon_factor_counts_of_prefixes(text, &[text.len()]);
+            assert_eq!(sparse, vec![core::duval(text).len()]);
+        }
+    }
+    let fibonacci = word::fibonacci_word(10);
+    let lengths = [1, 5, 34, fibonacci.len()];
+    let counts = core::lyndon_factor_counts_of_prefixes(&fibonacci, &lengths);
+    for (&length, count) in lengths.iter().zip(counts) {
+        assert_eq!(count, core::duval(&fibonacci[..length]).len());
+    }
+}
+
 /// only for even (counting starts at zero!) Fibonacci words, we have the property that the BWT has exactly two runs. See https://dx.doi.org/10.1007/978-3-319-23660-5_12
 #[test]
 fn test_bwt_from_text_by_sa() {
     for i in 1..8 {
         let text = word::fibonacci_word(2 * i);
-        let bwt = core::bwt_from_text_by_sa(&text);
-        let runs = core::number_of_runs(&mut bwt.as_slice());
+        let bwt = core::bwt_from_text_by_sa(&text).unwrap();
+        let runs = core::number_of_runs(&mut bwt.as_slice()).unwrap();
         assert_eq!(runs, 2);
     }
 }
 
+/// The binary words whose BWT (of the matrix of the conjugates) has exactly two runs are the
+/// conjugates of powers of Christoffel words, which include the standard Sturmian words.
+/// Sabrina Mantaci, Antonio Restivo, Marinella Sciortino: Burrows-Wheeler transform and Sturmian words. Inf. Process. Lett. 86(5): 241-246 (2003)
+#[test]
+fn test_bwt_sturmian() {
+    for (p, q) in [(1, 1), (2, 3), (5, 8), (7, 3), (13, 21), (1, 10)] {
+        for upper in [false, true] {
+            let text = word::christoffel_word(p, q, upper).unwrap();
+            let bwt = core::bwt_by_matrix(&text).unwrap();
+            assert_eq!(core::number_of_runs(&mut bwt.as_slice()).unwrap(), 2);
+        }
+    }
+    for directive in [
+        vec![1, 1, 1],
+        vec![2, 3],
+        vec![1, 2, 1, 3, 2],
+        vec![3, 1, 4, 1, 5],
+    ] {
+        let text = word::standard_word(&directive).unwrap();
+        let bwt = core::bwt_by_matrix(&text).unwrap();
+        assert_eq!(core::number_of_runs(&mut bwt.as_slice()).unwrap(), 2);
+    }
+}
+
 #[test]
 fn test_bwt_by_matrix() {
     pub const MAX_TEST_ITER: usize = 4096;
@@ -49,14 +102,248 @@ fn test_bwt_by_matrix() {
             continue;
         }
         let naive = core::bwt_by_matrix_naive(&text[0..text.len() - 1]);
-        let clever = core::bwt_by_matrix(&text[0..text.len() - 1]);
+        let clever = core::bwt_by_matrix(&text[0..text.len() - 1]).unwrap();
         if naive != clever {
-            core::bwt_by_matrix(&text[0..text.len() - 1]);
+            core::bwt_by_matrix(&text[0..text.len() - 1]).unwrap();
         }
         assert_eq!(naive, clever);
     }
 }
 
+#[test]
+fn test_suffixarray_prefix_doubling() {
+    for log_sigma in 1..4 {
+        for text in core::RandomStringGenerator::new(0..1024, log_sigma) {
+            let sa = core::suffixarray_prefix_doubling(&text).unwrap();
+            let naive: Vec<i32> = core::suffixarray_naive(&text)
+                .into_iter()
+                .map(|x| x as i32)
+                .collect();
+            assert_eq!(sa, naive);
+            //@ without the sentinel, shorter suffixes come first like in divsufsort
+            let prefix = &text[..text.len() - 1];
+            let mut divsufsort_sa = vec![0; prefix.len()];
+            cdivsufsort::sort_in_place(prefix, divsufsort_sa.as_mut_slice());
+            let symbols: Vec<u64> = prefix.iter().map(|&c| 70000 * c as u64).collect();
+            assert_eq!(
+                core::suffixarray_prefix_doubling(&symbols).unwrap(),
+                divsufsort_sa
+            );
+        }
+    }
+    let text: Vec<u32> = vec![300, 5, 300, 5, 300, 1_000_000, 5];
+    assert_eq!(
+        core::suffixarray_prefix_doubling(&text).unwrap(),
+        core::suffixarray_naive(&text)
+            .into_iter()
+            .map(|x| x as i32)
+            .collect::<Vec<i32>>()
+    );
+}
+
+#[test]
+fn test_bwt_integer() {
+    for text in core::RandomStringGenerator::new(0..1024, 2) {
+        let symbols: Vec<u64> = text.iter().map(|&c| c as u64).collect();
+        let bwt = core::bwt_from_text_by_sa(&text).unwrap();
+        let integer_bwt = core::bwt_from_integer_text_by_sa(&symbols).unwrap();
+        assert!(integer_bwt.iter().map(|&c| c as u8).eq(bwt.iter().copied()));
+        assert_eq!(
+            core::number_of_runs_in(&integer_bwt),
+            core::number_of_runs(&mut bwt.as_slice()).unwrap()
+        );
+        if text.len() >= 2 {
+            let prefix = &symbols[..symbols.len() - 1];
+            assert_eq!(
+                core::bwt_by_matrix_integer(prefix).unwrap(),
+                core::bwt_by_matrix_naive(prefix)
+            );
+        }
+    }
+}
+
+#[test]
+fn test_symbol_vector() {
+    use stringology::io;
+    let symbols: Vec<u64> = vec![0, 1, 255, 256, 65535, 7];
+    for width in [2, 4, 8] {
+        let mut bytes = Vec::new();
+        io::write_symbols(&mut bytes, &symbols, width).unwrap();
+        assert_eq!(bytes.len(), symbols.len() * width);
+        assert_eq!(io::byte_vector2symbols(&bytes, width).unwrap(), symbols);
+    }
+    assert_eq!(
+        io::byte_vector2symbols(&[1, 2, 3, 4], 2).unwrap(),
+        vec![0x0201, 0x0403]
+    );
+}
+
+#[test]
+fn test_mapped_bytes() {
+    use stringology::io;
+    let path = std::env::temp_dir().join(format!("stringology_mapped_{}", std::process::id()));
+    let text = word::fibonacci_word(10);
+    std::fs::write(&path, &text).unwrap();
+    let filename = path.to_str().unwrap();
+    for prefix_length in [0, 1, 17, text.len(), text.len() + 5] {
+        let mapped = io::file2mapped_bytes(filename, prefix_length).unwrap();
+        assert_eq!(
+            mapped.as_slice(),
+            io::file2byte_vector(filename, prefix_length)
+                .unwrap()
+                .as_slice()
+        );
+    }
+    std::fs::write(&path, b"").unwrap();
+    assert!(io::file2mapped_bytes(filename, 0).unwrap().is_empty());
+    std::fs::remove_file(&path).unwrap();
+}
+
+/// a reader returning its data in chunks of at most 3 bytes, interrupted before each chunk,
+/// and failing at the end if `fail` is set
+struct ChoppyReader<'a> {
+    data: &'a [u8],
+    interrupt: bool,
+    fail: bool,
+}
+
+impl std::io::Read for ChoppyReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(std::io::ErrorKind::Interrupted.into());
+        }
+        if self.data.is_empty() && self.fail {
+            return Err(std::io::Error::other("broken pipe"));
+        }
+        let length = std::cmp::min(std::cmp::min(3, buf.len()), self.data.len());
+        buf[..length].copy_from_slice(&self.data[..length]);
+        self.data = &self.data[length..];
+        Ok(length)
+    }
+}
+
+#[test]
+fn test_byte_reader() {
+    use stringology::io;
+    let text = word::fibonacci_word(12);
+    for capacity in [1, 2, 5, 1024] {
+        let reader = ChoppyReader {
+            data: &text,
+            interrupt: false,
+            fail: false,
+        };
+        let bytes: Vec<u8> = io::ByteReader::with_capacity(capacity, reader)
+            .map(|c| c.unwrap())
+            .collect();
+        assert_eq!(bytes, text);
+
+        let reader = ChoppyReader {
+            data: &text,
+            interrupt: false,
+            fail: true,
+        };
+        let mut bytes = io::ByteReader::with_capacity(capacity, reader);
+        for &c in text.iter() {
+            assert_eq!(bytes.next_byte().unwrap(), Some(c));
+        }
+        assert!(bytes.next_byte().is_err());
+    }
+    let mut empty: &[u8] = &[];
+    assert_eq!(
+        io::read_char(&mut empty).unwrap_err().kind(),
+        std::io::ErrorKind::UnexpectedEof
+    );
+    assert!(io::ByteReader::new(empty).next().is_none());
+}
+
+#[test]
+fn test_parse_records() {
+    use stringology::io;
+    let fasta = b">chr1 first record\nACGT\r\nacgn\n;comment\n>chr2\n\n>chr3\nTT\n";
+    let records = io::parse_fasta(fasta).unwrap();
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0].name, "chr1");
+    assert_eq!(records[0].sequence, b"ACGTacgn");
+    assert!(records[1].sequence.is_empty());
+    assert_eq!(records[2].sequence, b"TT");
+
+    let fastq = b"@read1 x\nACGT\n+\nIIII\n@read2\nNN\n+read2\n!!\n";
+    let records = io::parse_fastq(fastq).unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].name, "read2");
+    assert_eq!(records[1].sequence, b"NN");
+
+    let records = io::parse_lines(b"abc\n\nde\n");
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[1].name, "3");
+    assert_eq!(io::concatenate_records(&records, Some(b'$')), b"abc$de$");
+    assert_eq!(io::concatenate_records(&records, None), b"abcde");
+    assert_eq!(io::parse_separator("$"), Ok(b'$'));
+    assert_eq!(io::parse_separator("\\n"), Ok(b'\n'));
+    assert_eq!(io::parse_separator("36"), Ok(b'$'));
+    assert!(io::parse_separator("256").is_err());
+    assert!(io::parse_separator("ab").is_err());
+    assert_eq!(
+        io::generalized_text(&records),
+        vec![
+            b'a' as u64 + 2,
+            b'b' as u64 + 2,
+            b'c' as u64 + 2,
+            0,
+            b'd' as u64 + 2,
+            b'e' as u64 + 2,
+            1
+        ]
+    );
+
+    let mut sequence = b"acGTNnxT".to_vec();
+    io::fold_dna(&mut sequence);
+    assert_eq!(sequence, b"ACGTT");
+}
+
+#[test]
+fn test_compressed_input() {
+    use std::io::{Read, Write};
+    use stringology::io;
+    let text = word::fibonacci_word(15);
+    let gzip = {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&text).unwrap();
+        encoder.finish().unwrap()
+    };
+    let xz = {
+        let mut encoder = xz2::write::XzEncoder::new(Vec::new(), 6);
+        encoder.write_all(&text).unwrap();
+        encoder.finish().unwrap()
+    };
+    let zstd = zstd::encode_all(text.as_slice(), 3).unwrap();
+    let path = std::env::temp_dir().join(format!("stringology_compressed_{}", std::process::id()));
+    let filename = path.to_str().unwrap();
+    for (compressed, compression) in [
+        (&gzip, io::Compression::Gzip),
+        (&xz, io::Compression::Xz),
+        (&zstd, io::Compression::Zstd),
+        (&text, io::Compression::None),
+    ] {
+        assert_eq!(io::detect_compression(compressed), compression);
+        std::fs::write(&path, compressed).unwrap();
+        assert_eq!(io::file2byte_vector(filename, 0).unwrap(), text);
+        assert_eq!(io::file2byte_vector(filename, 100).unwrap(), &text[..100]);
+        assert_eq!(
+            io::file2mapped_bytes(filename, 7).unwrap().as_slice(),
+            &text[..7]
+        );
+        let mut streamed = Vec::new();
+        io::stream_or_stdin(Some(filename))
+            .unwrap()
+            .read_to_end(&mut streamed)
+            .unwrap();
+        assert_eq!(streamed, text);
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
 #[test]
 fn test_lyndon_conjugate() {
     const MAX_TEST_ITER: usize = 4096;
@@ -111,3 +398,191 @@ fn test_period() {
         }
     }
 }
+
+#[test]
+fn test_batch_jobs() {
+    use stringology::batch;
+    use stringology::report;
+    let dir = std::env::temp_dir().join(format!("stringology_batch_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("sub")).unwrap();
+    for (name, text) in [("b.txt", "abab"), ("a.txt", "aab"), ("c.dat", "c")] {
+        std::fs::write(dir.join(name), text).unwrap();
+    }
+    let file = |name: &str| dir.join(name).to_str().unwrap().to_string();
+
+    //@ directories are expanded to their files, skipping subdirectories
+    assert_eq!(
+        batch::expand_input(dir.to_str().unwrap()).unwrap(),
+        vec![file("a.txt"), file("b.txt"), file("c.dat")]
+    );
+    assert_eq!(
+        batch::expand_input(&file("*.txt")).unwrap(),
+        vec![file("a.txt"), file("b.txt")]
+    );
+
+    let args = batch::BatchArgs {
+        inputs: vec![file("*.txt")],
+        prefixlengths: vec![2, 0],
+        threads: 3,
+    };
+    let jobs = batch::jobs(&Some(file("c.dat")), 7, &args).unwrap();
+    let expected: Vec<(String, usize)> = ["c.dat", "a.txt", "b.txt"]
+        .iter()
+        .flat_map(|name| [(file(name), 2), (file(name), 0)])
+        .collect();
+    assert_eq!(
+        jobs.iter()
+            .map(|job| (job.infilename.clone().unwrap(), job.prefixlength))
+            .collect::<Vec<_>>(),
+        expected
+    );
+
+    //@ the records are returned in the order of the jobs, regardless of the scheduling
+    let records = batch::run(&jobs, args.threads, |job| {
+        let text =
+            stringology::io::file2byte_vector(job.infilename.as_ref().unwrap(), job.prefixlength)?;
+        Ok(vec![report::ResultRecord::new(
+            "test",
+            "read",
+            job.infilename.as_ref().unwrap(),
+            text.len(),
+        )])
+    })
+    .unwrap();
+    assert_eq!(
+        records
+            .iter()
+            .map(|record| (record.input.clone(), record.n))
+            .collect::<Vec<_>>(),
+        vec![
+            (file("c.dat"), 1),
+            (file("c.dat"), 1),
+            (file("a.txt"), 2),
+            (file("a.txt"), 3),
+            (file("b.txt"), 2),
+            (file("b.txt"), 4)
+        ]
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_sweep_steps() {
+    use stringology::batch::SweepArgs;
+    let explicit = SweepArgs {
+        prefix_steps: vec![50, 10, 20],
+        ..Default::default()
+    };
+    assert_eq!(explicit.steps(100).unwrap(), vec![10, 20, 50]);
+    assert_eq!(explicit.steps(20).unwrap(), vec![10, 20]);
+    assert_eq!(explicit.steps(15).unwrap(), vec![10, 15]);
+    assert_eq!(explicit.steps(5).unwrap(), vec![5]);
+
+    let linear = SweepArgs {
+        linear_steps: Some(10),
+        ..Default::default()
+    };
+    assert_eq!(linear.steps(30).unwrap(), vec![10, 20, 30]);
+    assert_eq!(linear.steps(35).unwrap(), vec![10, 20, 30, 35]);
+    assert_eq!(linear.steps(0).unwrap(), vec![0]);
+
+    let geometric = SweepArgs {
+        geometric_steps: Some(2.0),
+        ..Default::default()
+    };
+    assert_eq!(geometric.steps(20).unwrap(), vec![1, 2, 4, 8, 16, 20]);
+    assert_eq!(geometric.next_step(5).unwrap(), Some(8));
+    let slow = SweepArgs {
+        geometric_steps: Some(1.1),
+        ..Default::default()
+    };
+    assert_eq!(slow.steps(5).unwrap(), vec![1, 2, 3, 4, 5]);
+    assert!(!SweepArgs::default().is_enabled());
+
+    //@ the steps must grow
+    let zero = SweepArgs {
+        linear_steps: Some(0),
+        ..Default::default()
+    };
+    assert!(zero.steps(10).is_err());
+    let constant = SweepArgs {
+        geometric_steps: Some(1.0),
+        ..Default::default()
+    };
+    assert!(constant.next_step(2).is_err());
+}
+
+#[test]
+fn test_legacy_options() {
+    use stringology::tools::{self, alias_command};
+    let entropy = alias_command::<tools::entropy::Args>("entropy", tools::entropy::legacy_options);
+    entropy.clone().debug_assert();
+    let matches = entropy
+        .try_get_matches_from(["entropy", "-o", "2"])
+        .unwrap();
+    assert_eq!(matches.get_one::<usize>("order"), Some(&2));
+    assert_eq!(matches.get_one::<String>("outfilename"), None);
+
+    let rle = alias_command::<tools::rle::Args>("rle", tools::rle::legacy_options);
+    rle.clone().debug_assert();
+    let matches = rle.try_get_matches_from(["rle", "-h"]).unwrap();
+    assert!(matches.get_flag("human"));
+
+    let randomstring = alias_command::<tools::randomstring::Args>(
+        "randomstring",
+        tools::randomstring::legacy_options,
+    );
+    randomstring.clone().debug_assert();
+    let matches = randomstring
+        .try_get_matches_from(["randomstring", "-a", "4", "-p", "100"])
+        .unwrap();
+    assert_eq!(matches.get_one::<usize>("sigma"), Some(&4));
+    assert_eq!(matches.get_one::<usize>("length"), Some(&100));
+    assert_eq!(matches.get_one::<usize>("prefixlength"), Some(&0));
+
+    let enumerate =
+        alias_command::<tools::enumerate::Args>("enumerate", tools::enumerate::legacy_options);
+    enumerate.clone().debug_assert();
+    let matches = enumerate
+        .try_get_matches_from(["enumerate", "2", "3", "5"])
+        .unwrap();
+    assert_eq!(matches.get_one::<u8>("sigma"), Some(&2));
+    assert_eq!(matches.get_one::<u64>("length"), Some(&3));
+    assert_eq!(matches.get_one::<u64>("max_length"), Some(&5));
+}
+
+#[test]
+fn test_errors() {
+    use stringology::error::Error;
+    use stringology::io;
+
+    //@ the zero byte is reserved for the sentinel at the end
+    let error = core::bwt_from_text_by_sa(&b"ab\0ab\0".to_vec()).unwrap_err();
+    assert!(matches!(error, Error::InvalidInput(_)));
+    assert_eq!(error.exit_code(), 65);
+    assert!(matches!(
+        core::bwt_from_text_by_sa(&Vec::new()),
+        Err(Error::InvalidInput(_))
+    ));
+
+    let missing = "/nonexistent/stringology/input.txt";
+    match io::file2byte_vector(missing, 0) {
+        Err(Error::File { path, source }) => {
+            assert_eq!(path, missing);
+            assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+        }
+        result => panic!("expected a file error, got {:?}", result),
+    }
+    assert_eq!(
+        io::file2mapped_bytes(missing, 0).unwrap_err().exit_code(),
+        74
+    );
+    assert!(matches!(
+        io::byte_vector2symbols(&[1, 2, 3], 2),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        io::byte_vector2symbols(&[1, 2, 3], 3),
+        Err(Error::InvalidArgument(_))
+    ));
+}
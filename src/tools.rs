@@ -0,0 +1,126 @@
+//! The command line tools, each with its arguments and its entry point `run`.
+//! They are the subcommands of the `stringology` binary, and are also available as binaries of their own.
+use crate::error::{Error, Result};
+use crate::report;
+use clap::{Args as _, FromArgMatches as _};
+
+pub mod bwt;
+pub mod count_lexparse;
+pub mod count_r;
+pub mod count_runs;
+pub mod count_sigma;
+pub mod count_z;
+pub mod entropy;
+pub mod entropy_kmer;
+pub mod enumerate;
+pub mod enumerate_lexparse;
+pub mod escape;
+pub mod fm_search;
+pub mod generator_kmer;
+pub mod is_stringattractor;
+pub mod lyndonfactorization;
+pub mod lyndonwords;
+pub mod mtf;
+pub mod mus;
+pub mod randomstring;
+pub mod reverse;
+pub mod rindex;
+pub mod rle;
+pub mod search;
+pub mod sensitivity;
+pub mod stats;
+pub mod suffixautomaton;
+pub mod word;
+
+/// the options shared by all tools
+#[derive(clap::Args, Debug, Clone)]
+pub struct GlobalArgs {
+    /// the input file to read (otherwise read from stdin)
+    #[arg(short, long, global = true)]
+    pub infilename: Option<String>,
+
+    /// the output file to write (otherwise write to stdout, or skip the output for tools reporting result records)
+    #[arg(short, long, global = true)]
+    pub outfilename: Option<String>,
+
+    /// the length of the prefix to parse (0 for the entire input)
+    #[arg(short, long, global = true, default_value_t = 0)]
+    pub prefixlength: usize,
+
+    /// the format of the result records
+    #[arg(long, global = true, value_enum, default_value_t = report::OutputFormat::Result)]
+    pub format: report::OutputFormat,
+
+    /// log more details (-v: info, -vv: debug, -vvv: trace), overriding RUST_LOG
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+impl GlobalArgs {
+    /// initializes the logger with the level given by RUST_LOG or the verbosity
+    pub fn init_logging(&self) {
+        let mut builder = env_logger::Builder::from_default_env();
+        match self.verbose {
+            0 => &mut builder,
+            1 => builder.filter_level(log::LevelFilter::Info),
+            2 => builder.filter_level(log::LevelFilter::Debug),
+            _ => builder.filter_level(log::LevelFilter::Trace),
+        };
+        builder.init();
+    }
+
+    /// rejects a prefix length for the tool `name` that does not read an input
+    pub fn check_no_prefixlength(&self, name: &str) -> Result<()> {
+        if self.prefixlength != 0 {
+            return Err(Error::InvalidArgument(format!(
+                "{} does not read an input, such that the prefix length -p has no meaning",
+                name
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Exits the binary `name` with a message on stderr and the exit code of the error if `result`
+/// is an error.
+pub fn exit_on_error(name: &str, result: Result<()>) {
+    if let Err(error) = result {
+        eprintln!("{}: error: {}", name, error);
+        std::process::exit(error.exit_code());
+    }
+}
+
+/// The entry point of the binary `name` running a single tool, which takes the global options
+/// and the arguments `A` of the tool.
+pub fn run_alias<A: clap::Args + clap::FromArgMatches>(
+    name: &'static str,
+    run: fn(&GlobalArgs, &A) -> Result<()>,
+) {
+    run_legacy_alias(name, |command| command, run)
+}
+
+/// the command line of the binary `name` with the global options and the arguments `A`, adapted
+/// by `legacy`
+pub fn alias_command<A: clap::Args>(
+    name: &'static str,
+    legacy: fn(clap::Command) -> clap::Command,
+) -> clap::Command {
+    let command = clap::Command::new(name)
+        .version(clap::crate_version!())
+        .author(clap::crate_authors!());
+    legacy(A::augment_args(GlobalArgs::augment_args(command)))
+}
+
+/// Like [`run_alias`], where `legacy` adapts the command line to the options of the former binary
+/// `name` whose short options or positional arguments differ from those of the tool.
+pub fn run_legacy_alias<A: clap::Args + clap::FromArgMatches>(
+    name: &'static str,
+    legacy: fn(clap::Command) -> clap::Command,
+    run: fn(&GlobalArgs, &A) -> Result<()>,
+) {
+    let matches = alias_command::<A>(name, legacy).get_matches();
+    let global = GlobalArgs::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let args = A::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    global.init_logging();
+    exit_on_error(name, run(&global, &args));
+}
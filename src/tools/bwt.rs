@@ -0,0 +1,56 @@
+use log::info;
+
+use super::GlobalArgs;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+
+/// computes the BWT via divsufsort
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// use the BWT matrix
+    #[arg(short, long)]
+    use_matrix: bool,
+
+    /// do not append a null byte at the end acting as the dollar sign in common papers
+    #[arg(short, long)]
+    no_dollar: bool,
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    info!("no_dollar?: {}", args.no_dollar);
+    info!("use matrix?: {}", args.use_matrix);
+    info!("prefixlength: {}", global.prefixlength);
+
+    info!("read text");
+    let text = if args.no_dollar {
+        io::file_or_stdin2byte_vector(
+            core::stringopt_stropt(&global.infilename),
+            global.prefixlength,
+        )?
+    } else {
+        let mut text = io::file_or_stdin2byte_vector(
+            core::stringopt_stropt(&global.infilename),
+            global.prefixlength,
+        )?;
+        text.push(0u8);
+        text
+    };
+
+    info!("build bwt");
+    let bwt = match args.use_matrix {
+        true => {
+            if text.len() < 100 {
+                core::bwt_by_matrix_naive(&text)
+            } else {
+                core::bwt_by_matrix(&text)?
+            }
+        }
+        false => core::bwt_from_text_by_sa(&text)?,
+    };
+
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+    writer.write_all(bwt.as_slice())?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+
+use log::{debug, info, log_enabled, Level};
+
+#[derive(Debug)]
+pub(crate) struct LZFactor {
+    pos: u32,
+    len: u32,
+}
+
+/// Computes the Lempel-Ziv 77 factorization
+/// Reference: Enno Ohlebusch, Simon Gog: "Lempel-Ziv Factorization Revisited". CPM 2011: 15-26
+pub(crate) fn compute_lexparse(text: &[u8], plcp: &[u32], phi: &[i32]) -> Vec<LZFactor> {
+    // LZ77 computation
+    let mut factors = Vec::new();
+    let mut i = 0;
+    while i < text.len() {
+        //@ last character is a dummy character -> do not encode
+        if plcp[i] == 0 {
+            factors.push(LZFactor {
+                len: 0,
+                pos: text[i] as u32,
+            });
+            i += 1;
+            continue;
+        }
+        factors.push(LZFactor {
+            len: plcp[i],
+            pos: phi[i] as u32,
+        });
+        i += plcp[i] as usize;
+    }
+    factors
+}
+
+/// computes the number of factors in lex-parse
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+}
+
+/// computes the lex-parse of the input of `job`
+fn run_job(global: &GlobalArgs, job: &batch::Job) -> Result<Vec<report::ResultRecord>> {
+    info!("filename: {}", core::get_filename(&job.infilename));
+    info!("prefixlength: {}", job.prefixlength);
+
+    use std::time::Instant;
+
+    info!("Build DS");
+    let mut now = Instant::now();
+
+    let text =
+        io::file_or_stdin2mapped_bytes(core::stringopt_stropt(&job.infilename), job.prefixlength)?;
+
+    let factors = if text.is_empty() {
+        Vec::new()
+    } else {
+        let sa = {
+            let mut sa = vec![0; text.len()];
+            cdivsufsort::sort_in_place(&text, sa.as_mut_slice());
+            sa
+        };
+        if log_enabled!(Level::Debug) {
+            debug!(" T : {:?}", text);
+            debug!("sa : {:?}", sa);
+        }
+        let phi = core::compute_phi(sa.as_slice());
+        let plcp = core::compute_plcp(text.as_slice(), phi.as_slice());
+
+        info!("time: {}", now.elapsed().as_millis());
+
+        now = Instant::now();
+        info!("run lexparse");
+        compute_lexparse(&text, &plcp, &phi)
+    };
+    debug!("Lex-Parse {:?}", factors);
+    // debug_assert_eq!(text, decode_lz77(factors.as_slice()));
+
+    let record = report::ResultRecord::new(
+        "count_lexparse",
+        "lexparse",
+        core::get_filename(&job.infilename),
+        text.len(),
+    )
+    .with("factors", factors.len())
+    .timed(&now);
+
+    if let Some(filename) = &global.outfilename {
+        let mut writer = io::stream_or_stdout(Some(filename))?;
+        for fact in factors {
+            writer.write_all(format!("({},{})", fact.pos, fact.len).as_bytes())?;
+        }
+    }
+    Ok(vec![record])
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::check_single_output(&jobs, &global.outfilename)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(global, job)
+    })
+}
@@ -0,0 +1,228 @@
+use log::info;
+
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::{Error, Result};
+use crate::io;
+use crate::report;
+
+use std::time::Instant;
+
+/// computes the number of runs in the BWT
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// use the BWT matrix
+    #[arg(short, long)]
+    use_matrix: bool,
+
+    /// do not append a null byte at the end acting as the dollar sign in common papers
+    #[arg(short, long)]
+    no_dollar: bool,
+
+    /// the number of bytes per symbol, read in little-endian order (1, 2, 4 or 8)
+    #[arg(short = 'w', long, default_value_t = 1)]
+    symbol_width: usize,
+
+    #[command(flatten)]
+    collection: io::CollectionArgs,
+
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+
+    #[command(flatten)]
+    sweep: batch::SweepArgs,
+}
+
+/// the length of the BWT and its number of runs for a byte text
+fn bwt_runs_of_bytes(mut text: Vec<u8>, args: &Args) -> Result<(usize, usize)> {
+    if !args.no_dollar {
+        text.push(0u8);
+    }
+    info!("build bwt");
+    let bwt = match args.use_matrix {
+        true => {
+            if text.len() < 100 {
+                core::bwt_by_matrix_naive(&text)
+            } else {
+                core::bwt_by_matrix(&text)?
+            }
+        }
+        false => core::bwt_from_text_by_sa(&text)?,
+    };
+    Ok((bwt.len(), core::number_of_runs_in(&bwt)))
+}
+
+/// the length of the BWT and its number of runs for an integer text
+fn bwt_runs_of_symbols(symbols: Vec<u64>, args: &Args) -> Result<(usize, usize)> {
+    info!("build bwt");
+    let text = if args.no_dollar {
+        symbols
+    } else {
+        core::integer_text_with_sentinel(&symbols)?
+    };
+    let bwt = match args.use_matrix {
+        true => core::bwt_by_matrix_integer(&text)?,
+        false => core::bwt_from_integer_text_by_sa(&text)?,
+    };
+    Ok((bwt.len(), core::number_of_runs_in(&bwt)))
+}
+
+/// the length of the BWT and its number of runs for the generalized text of `records`,
+/// where all sentinels are counted as the same symbol
+fn bwt_runs_of_collection(records: &[io::Record]) -> Result<(usize, usize)> {
+    info!("build generalized bwt");
+    let text = io::generalized_text(records);
+    let sa: Vec<usize> = core::suffixarray_prefix_doubling(&text)?
+        .into_iter()
+        .map(|x| x as usize)
+        .collect();
+    let sentinels = records.len() as u64;
+    let bwt: Vec<u64> = core::bwt_from_sa(&text, &sa)
+        .into_iter()
+        .map(|c| if c < sentinels { 0 } else { c })
+        .collect();
+    Ok((bwt.len(), core::number_of_runs_in(&bwt)))
+}
+
+/// the result record of a BWT of length `bwt_length` with `r` runs of a text of length `n`,
+/// computed since `now`
+fn result_record(
+    args: &Args,
+    job: &batch::Job,
+    now: &Instant,
+    (n, bwt_length, r): (usize, usize, usize),
+    record: Option<&str>,
+) -> report::ResultRecord {
+    let mut result =
+        report::ResultRecord::new("count_r", "bwt", core::get_filename(&job.infilename), n)
+            .with("bwt_length", bwt_length)
+            .with("bwt_runs", r)
+            .with("no_dollar", args.no_dollar)
+            .with("use_matrix", args.use_matrix);
+    if let Some(name) = record {
+        result.push("record", name);
+    }
+    result.timed(now)
+}
+
+/// computes the length of the indexed text and its number of BWT runs
+type BwtRuns<T> = fn(Vec<T>, &Args) -> Result<(usize, usize)>;
+
+/// The result records of the BWT runs of `text` computed by `bwt_runs`, or of the prefixes of
+/// `text` in a sweep, where the BWT of each prefix is computed from scratch.
+fn bwt_runs_records<T: Clone>(
+    args: &Args,
+    job: &batch::Job,
+    text: Vec<T>,
+    record: Option<&str>,
+    bwt_runs: BwtRuns<T>,
+) -> Result<Vec<report::ResultRecord>> {
+    if !args.sweep.is_enabled() {
+        let now = Instant::now();
+        let n = text.len();
+        let (bwt_length, r) = bwt_runs(text, args)?;
+        return Ok(vec![result_record(
+            args,
+            job,
+            &now,
+            (n, bwt_length, r),
+            record,
+        )]);
+    }
+    args.sweep
+        .steps(text.len())?
+        .into_iter()
+        .map(|prefix_length| {
+            let now = Instant::now();
+            let (bwt_length, r) = bwt_runs(text[..prefix_length].to_vec(), args)?;
+            Ok(result_record(
+                args,
+                job,
+                &now,
+                (prefix_length, bwt_length, r),
+                record,
+            ))
+        })
+        .collect()
+}
+
+/// counts the BWT runs of the input of `job`
+fn run_job(args: &Args, job: &batch::Job) -> Result<Vec<report::ResultRecord>> {
+    info!("filename: {}", core::get_filename(&job.infilename));
+    info!("prefixlength: {}", job.prefixlength);
+
+    info!("read text");
+    if args.symbol_width != 1 {
+        let symbols = io::file_or_stdin2symbol_vector(
+            core::stringopt_stropt(&job.infilename),
+            job.prefixlength,
+            args.symbol_width,
+        )?;
+        bwt_runs_records(args, job, symbols, None, bwt_runs_of_symbols)
+    } else if args.collection.is_plain() {
+        let text = io::file_or_stdin2byte_vector(
+            core::stringopt_stropt(&job.infilename),
+            job.prefixlength,
+        )?;
+        bwt_runs_records(args, job, text, None, bwt_runs_of_bytes)
+    } else {
+        let input = io::file_or_stdin2mapped_bytes(
+            core::stringopt_stropt(&job.infilename),
+            job.prefixlength,
+        )?;
+        let records = args.collection.records(&input)?;
+        info!("number of records: {}", records.len());
+        match args.collection.collection {
+            io::CollectionMode::Concatenate => {
+                let text = io::concatenate_records(&records, args.collection.separator);
+                bwt_runs_records(args, job, text, None, bwt_runs_of_bytes)
+            }
+            io::CollectionMode::PerRecord => {
+                let mut results = Vec::new();
+                for record in records {
+                    results.extend(bwt_runs_records(
+                        args,
+                        job,
+                        record.sequence,
+                        Some(&record.name),
+                        bwt_runs_of_bytes,
+                    )?);
+                }
+                Ok(results)
+            }
+            io::CollectionMode::Generalized => {
+                if args.sweep.is_enabled() {
+                    return Err(Error::InvalidArgument(String::from(
+                        "prefix sweeps are not supported for generalized collections",
+                    )));
+                }
+                if args.use_matrix || args.no_dollar {
+                    return Err(Error::InvalidArgument(String::from(
+                        "--use-matrix and --no-dollar are not supported for generalized collections, whose BWT is computed by the suffix array with a sentinel per record",
+                    )));
+                }
+                let now = Instant::now();
+                let n = records.iter().map(|record| record.sequence.len()).sum();
+                let (bwt_length, r) = bwt_runs_of_collection(&records)?;
+                Ok(vec![result_record(
+                    args,
+                    job,
+                    &now,
+                    (n, bwt_length, r),
+                    None,
+                )])
+            }
+        }
+    }
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    info!("no_dollar?: {}", args.no_dollar);
+    info!("use matrix?: {}", args.use_matrix);
+
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(args, job)
+    })
+}
@@ -0,0 +1,29 @@
+use super::GlobalArgs;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+
+/// computes the number of character runs in a text
+#[derive(clap::Args, Debug)]
+pub struct Args {}
+
+pub fn run(global: &GlobalArgs, _args: &Args) -> Result<()> {
+    let now = std::time::Instant::now();
+    let mut reader = io::prefix_stream_or_stdin(
+        core::stringopt_stropt(&global.infilename),
+        global.prefixlength,
+    )?;
+    let mut counter = io::CountingReader::new(&mut reader);
+    let runs = core::number_of_runs(&mut counter)?;
+    let record = report::ResultRecord::new(
+        "count_runs",
+        "runs",
+        core::get_filename(&global.infilename),
+        counter.count(),
+    )
+    .with("runs", runs)
+    .timed(&now);
+    report::Reporter::new(global.format).emit(record);
+    Ok(())
+}
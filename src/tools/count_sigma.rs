@@ -0,0 +1,67 @@
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+
+use log::info;
+
+/// counts the number of different characters in a byte string
+// fn count_sigma(s : &[u8]) -> u8 {
+pub(super) fn count_sigma<'a, I: Iterator<Item = &'a u8>>(text_iter: I) -> u8 {
+    let mut inc = 0_u8;
+    let mut alphabet_to_reduced: Vec<u8> = vec![0; u8::MAX as usize + 1]; //@ maps a u8 char to its symbol of the effective alphabet, we start at 1 since 0 is treated as a special symbol
+    for c in text_iter {
+        let index = *c as usize;
+        if alphabet_to_reduced[index] == 0 {
+            inc += 1;
+            alphabet_to_reduced[index] = inc;
+        }
+    }
+    inc
+}
+
+#[test]
+fn test_count_sigma() {
+    assert_eq!(count_sigma(b"".iter()), 0);
+    assert_eq!(count_sigma(b"aaa".iter()), 1);
+    assert_eq!(count_sigma(b"aba".iter()), 2);
+    assert_eq!(count_sigma(b"abc".iter()), 3);
+}
+
+/// computes the number of distinct characters in a byte text
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+}
+
+/// counts the distinct characters of the input of `job`
+fn run_job(job: &batch::Job) -> Result<Vec<report::ResultRecord>> {
+    info!("filename: {}", core::get_filename(&job.infilename));
+    info!("prefixlength: {}", job.prefixlength);
+
+    use std::time::Instant;
+    let now = Instant::now();
+    info!("read text");
+    let text =
+        io::file_or_stdin2mapped_bytes(core::stringopt_stropt(&job.infilename), job.prefixlength)?;
+
+    info!("compute sigma");
+    let sigma = count_sigma(text.iter());
+
+    Ok(vec![report::ResultRecord::new(
+        "count_sigma",
+        "count_sigma",
+        core::get_filename(&job.infilename),
+        text.len(),
+    )
+    .with("sigma", sigma)
+    .timed(&now)])
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, run_job)
+}
@@ -0,0 +1,382 @@
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+use crate::rmq::{RangeMinimumQuery, SuccinctRMQ};
+use more_asserts::{debug_assert_gt, debug_assert_lt};
+
+use log::{debug, info, log_enabled, Level};
+
+#[derive(Debug)]
+pub(crate) struct LZFactor {
+    /// the referred distance, or the literal character if `len` is zero
+    pos: u64,
+    len: u32,
+}
+
+fn decode_lz77(factors: &[LZFactor]) -> Vec<u64> {
+    let mut text = Vec::new();
+    for factor in factors.iter() {
+        if factor.len == 0 {
+            text.push(factor.pos);
+        } else {
+            let ref_pos = text.len() - factor.pos as usize;
+            for p in 0..factor.len as usize {
+                text.push(text[ref_pos + p]);
+            }
+        }
+    }
+    // text.push(0);
+    text
+}
+
+/// Computes the Lempel-Ziv 77 factorization
+/// Reference: Enno Ohlebusch, Simon Gog: "Lempel-Ziv Factorization Revisited". CPM 2011: 15-26
+fn compute_lz77<C: Copy + Into<u64>, R: RangeMinimumQuery<u32>>(
+    text: &[C],
+    lcprmq: &R,
+    sa: &[i32],
+    isa: &[i32],
+    nsv: &[u32],
+    psv: &[u32],
+) -> Vec<LZFactor> {
+    let lcp = lcprmq.array();
+    // LZ77 computation
+    let mut factors = Vec::new();
+    let mut i = 0;
+    while i < text.len() {
+        //@ last character is a dummy character -> do not encode
+        let sa_position = isa[i] as usize;
+
+        let prev_lcp = if psv[sa_position] == core::INVALID_VALUE {
+            0
+        } else {
+            let psv_sa_position = psv[sa_position] as usize;
+            debug_assert_gt!(sa_position, psv_sa_position);
+            let ret = lcprmq.query(psv_sa_position + 1, sa_position + 1);
+            debug_assert_eq!(
+                ret,
+                (psv_sa_position + 1..sa_position + 1)
+                    .map(|x| { lcp[x] })
+                    .min()
+                    .unwrap()
+            );
+            ret
+        };
+        let next_lcp = if nsv[sa_position] == core::INVALID_VALUE {
+            0
+        } else {
+            let nsv_sa_position = nsv[sa_position] as usize;
+            debug_assert_lt!(sa_position, nsv_sa_position);
+            let ret = lcprmq.query(sa_position + 1, nsv_sa_position + 1);
+            debug_assert_eq!(
+                ret,
+                (sa_position + 1..nsv_sa_position + 1)
+                    .map(|x| { lcp[x] })
+                    .min()
+                    .unwrap()
+            );
+            ret
+        };
+
+        if prev_lcp == 0 && next_lcp == 0 {
+            // assert!(false); // should not happen
+            factors.push(LZFactor {
+                len: 0,
+                pos: text[i].into(),
+            });
+            i += 1;
+            continue;
+        }
+        let max_lcp = if prev_lcp < next_lcp {
+            next_lcp
+        } else {
+            prev_lcp
+        };
+        let max_pos = if prev_lcp < next_lcp {
+            sa[nsv[sa_position] as usize]
+        } else {
+            sa[psv[sa_position] as usize]
+        };
+        debug_assert_lt!(max_pos as usize, i);
+        factors.push(LZFactor {
+            len: max_lcp,
+            pos: (i - max_pos as usize) as u64,
+        });
+        i += max_lcp as usize;
+    }
+    factors
+}
+
+/// The number of LZ77 factors of each prefix of the factorized text whose length is in the
+/// increasing sequence `lengths`. Since a factor is the longest previous factor starting at its
+/// position, the factorization of a prefix consists of the factors starting in the prefix,
+/// where the last one is cut at the end of the prefix.
+fn lz77_factor_counts_of_prefixes(factors: &[LZFactor], lengths: &[usize]) -> Vec<usize> {
+    let mut counts = Vec::with_capacity(lengths.len());
+    let mut factor_index = 0;
+    let mut factor_start = 0;
+    for &length in lengths {
+        while factor_index < factors.len() && factor_start < length {
+            factor_start += std::cmp::max(factors[factor_index].len as usize, 1);
+            factor_index += 1;
+        }
+        counts.push(factor_index);
+    }
+    counts
+}
+
+pub const MAX_TEST_ITER: usize = 4096;
+
+#[test]
+#[allow(clippy::unnecessary_cast)]
+fn test_compute_lz77() {
+    use crate::rmq::SparseTable;
+    for text in core::RandomStringGenerator::new(0..MAX_TEST_ITER as usize, 1) {
+        // text.push(0u8);
+        let n = text.len();
+        let sa = {
+            let mut sa = vec![0; n];
+            cdivsufsort::sort_in_place(&text, sa.as_mut_slice());
+            sa
+        };
+
+        let isa = core::inverse_permutation(sa.as_slice());
+        let psv = core::compute_psv(sa.as_slice());
+        let nsv = core::compute_nsv(sa.as_slice());
+        let lcp = {
+            let phi = core::compute_phi(sa.as_slice());
+            let plcp = core::compute_plcp(text.as_slice(), phi.as_slice());
+            core::compute_lcp(plcp.as_slice(), sa.as_slice())
+        };
+        let factors = compute_lz77(&text, &SuccinctRMQ::new(&lcp), &sa, &isa, &nsv, &psv);
+        println!("LZ77 {:?}", factors);
+        let symbols: Vec<u64> = text.iter().map(|&c| c as u64).collect();
+        assert_eq!(symbols, decode_lz77(factors.as_slice()));
+        let sparse_table_factors =
+            compute_lz77(&text, &SparseTable::new(&lcp), &sa, &isa, &nsv, &psv);
+        assert_eq!(factors.len(), sparse_table_factors.len());
+        assert_eq!(symbols, decode_lz77(sparse_table_factors.as_slice()));
+
+        //@ the factorization only depends on the order of the characters
+        let symbols: Vec<u64> = text.iter().map(|&c| 1000 * c as u64).collect();
+        let integer_sa = core::suffixarray_prefix_doubling(&symbols).unwrap();
+        assert_eq!(integer_sa, sa);
+        let integer_factors =
+            compute_lz77(&symbols, &SuccinctRMQ::new(&lcp), &sa, &isa, &nsv, &psv);
+        assert_eq!(symbols, decode_lz77(integer_factors.as_slice()));
+        assert_eq!(factors.len(), integer_factors.len());
+
+        //@ the factorizations of all prefixes can be read off the factorization of the text
+        let lengths: Vec<usize> = (0..=n).collect();
+        let counts = lz77_factor_counts_of_prefixes(&factors, &lengths);
+        for (&length, count) in lengths.iter().zip(counts) {
+            let prefix = &text[..length];
+            let prefix_factors = if length == 0 {
+                Vec::new()
+            } else {
+                let mut now = Instant::now();
+                compute_factors(prefix, &byte_suffix_array(prefix), &mut now)
+            };
+            assert_eq!(count, prefix_factors.len());
+        }
+    }
+}
+
+use std::time::Instant;
+/// computes the number of LZ77 factors
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the number of bytes per symbol, read in little-endian order (1, 2, 4 or 8)
+    #[arg(short = 'w', long, default_value_t = 1)]
+    symbol_width: usize,
+
+    #[command(flatten)]
+    collection: io::CollectionArgs,
+
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+
+    #[command(flatten)]
+    sweep: batch::SweepArgs,
+}
+
+/// the suffix array of a byte text
+pub(crate) fn byte_suffix_array(text: &[u8]) -> Vec<i32> {
+    let mut sa = vec![0; text.len()];
+    cdivsufsort::sort_in_place(text, sa.as_mut_slice());
+    sa
+}
+
+/// computes the LZ77 factorization of the input of `job`
+fn run_job(
+    global: &GlobalArgs,
+    args: &Args,
+    job: &batch::Job,
+) -> Result<Vec<report::ResultRecord>> {
+    info!("filename: {}", core::get_filename(&job.infilename));
+    info!("prefixlength: {}", job.prefixlength);
+
+    let mut writer = match &global.outfilename {
+        Some(filename) => Some(io::stream_or_stdout(Some(filename))?),
+        None => None,
+    };
+    let mut results = Vec::new();
+
+    if args.symbol_width != 1 {
+        let text = io::file_or_stdin2symbol_vector(
+            core::stringopt_stropt(&job.infilename),
+            job.prefixlength,
+            args.symbol_width,
+        )?;
+        let sa = core::suffixarray_prefix_doubling(&text)?;
+        let factors = factorize(&text, sa, args, job, &mut results, None)?;
+        write_factors(&mut writer, &factors)?;
+        return Ok(results);
+    }
+    let input =
+        io::file_or_stdin2mapped_bytes(core::stringopt_stropt(&job.infilename), job.prefixlength)?;
+    if args.collection.is_plain() {
+        let sa = byte_suffix_array(&input);
+        let factors = factorize(&input, sa, args, job, &mut results, None)?;
+        write_factors(&mut writer, &factors)?;
+        return Ok(results);
+    }
+    let records = args.collection.records(&input)?;
+    info!("number of records: {}", records.len());
+    match args.collection.collection {
+        io::CollectionMode::Concatenate => {
+            let text = io::concatenate_records(&records, args.collection.separator);
+            let sa = byte_suffix_array(&text);
+            let factors = factorize(&text, sa, args, job, &mut results, None)?;
+            write_factors(&mut writer, &factors)?;
+        }
+        io::CollectionMode::PerRecord => {
+            for record in records {
+                let sa = byte_suffix_array(&record.sequence);
+                let factors = factorize(
+                    &record.sequence,
+                    sa,
+                    args,
+                    job,
+                    &mut results,
+                    Some(&record.name),
+                )?;
+                write_factors(&mut writer, &factors)?;
+                if let Some(writer) = writer.as_mut() {
+                    writer.write_all(b"\n")?;
+                }
+            }
+        }
+        io::CollectionMode::Generalized => {
+            //@ the distinct sentinels prevent factors from crossing record boundaries
+            let text = io::generalized_text(&records);
+            let sa = core::suffixarray_prefix_doubling(&text)?;
+            let factors = factorize(&text, sa, args, job, &mut results, None)?;
+            write_factors(&mut writer, &factors)?;
+        }
+    }
+    Ok(results)
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::check_single_output(&jobs, &global.outfilename)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(global, args, job)
+    })
+}
+
+/// writes the factors as pairs (pos,len) if an output file is given
+fn write_factors(
+    writer: &mut Option<Box<dyn std::io::Write>>,
+    factors: &[LZFactor],
+) -> std::io::Result<()> {
+    if let Some(writer) = writer.as_mut() {
+        for fact in factors {
+            writer.write_all(format!("({},{})", fact.pos, fact.len).as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+/// computes the LZ77 factorization of `text` with suffix array `sa`, and appends its result
+/// records to `results`, tagged with the name of the record if given
+fn factorize<C: Copy + Eq + Into<u64> + std::fmt::Debug>(
+    text: &[C],
+    sa: Vec<i32>,
+    args: &Args,
+    job: &batch::Job,
+    results: &mut Vec<report::ResultRecord>,
+    record: Option<&str>,
+) -> Result<Vec<LZFactor>> {
+    info!("Build DS");
+    let mut now = Instant::now();
+
+    let result = |length: usize, factors: usize| {
+        let mut result = report::ResultRecord::new(
+            "count_z",
+            "lz77",
+            core::get_filename(&job.infilename),
+            length,
+        )
+        .with("factors", factors);
+        if let Some(name) = record {
+            result.push("record", name);
+        }
+        result
+    };
+    let factors = if text.is_empty() {
+        Vec::new()
+    } else {
+        compute_factors(text, &sa, &mut now)
+    };
+
+    if args.sweep.is_enabled() {
+        let steps = args.sweep.steps(text.len())?;
+        let counts = lz77_factor_counts_of_prefixes(&factors, &steps);
+        for (&length, count) in steps.iter().zip(counts) {
+            results.push(result(length, count).timed(&now));
+        }
+    } else {
+        results.push(result(sa.len(), factors.len()).timed(&now));
+    }
+    Ok(factors)
+}
+
+/// computes the LZ77 factorization of the non-empty `text` with suffix array `sa`, resetting
+/// `now` after building the data structures
+pub(crate) fn compute_factors<C: Copy + Eq + Into<u64> + std::fmt::Debug>(
+    text: &[C],
+    sa: &[i32],
+    now: &mut Instant,
+) -> Vec<LZFactor> {
+    if log_enabled!(Level::Debug) {
+        debug!(" T : {:?}", text);
+        debug!("sa : {:?}", sa);
+    }
+
+    let isa = core::inverse_permutation(sa);
+    let psv = core::compute_psv(sa);
+    let nsv = core::compute_nsv(sa);
+    let lcp = {
+        let phi = core::compute_phi(sa);
+        let plcp = core::compute_plcp(text, phi.as_slice());
+        core::compute_lcp(plcp.as_slice(), sa)
+    };
+    let lcprmq = SuccinctRMQ::new(&lcp);
+
+    info!("time: {}", now.elapsed().as_millis());
+
+    *now = Instant::now();
+    info!("run LZ77");
+    let factors = compute_lz77(text, &lcprmq, sa, &isa, &nsv, &psv);
+    debug!("LZ77 {:?}", factors);
+    debug_assert!(text
+        .iter()
+        .map(|&c| c.into())
+        .eq(decode_lz77(factors.as_slice()).into_iter()));
+    factors
+}
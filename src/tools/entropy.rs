@@ -0,0 +1,275 @@
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+use more_asserts::assert_gt;
+
+#[cfg(test)]
+use approx::assert_abs_diff_eq;
+use log::info;
+
+/// the empirical entropy of a distribution given by the positive counts of its characters
+fn entropy_of_counts<I: Iterator<Item = usize>>(counts: I, total_count: usize) -> f64 {
+    let mut sum = 0 as f64;
+    for count in counts {
+        if count > 0 {
+            sum += (count as f64) * ((total_count as f64 / count as f64).log2());
+        }
+    }
+    sum / (total_count as f64)
+}
+
+pub(super) fn zero_order_entropy<'a, I: Iterator<Item = &'a u8>>(text_iter: I) -> f64 {
+    let mut char_counters: Vec<usize> = vec![0; u8::MAX as usize + 1];
+    let mut total_count = 0;
+    for c in text_iter {
+        let index: usize = (*c).into();
+        char_counters[index] += 1;
+        total_count += 1;
+    }
+    entropy_of_counts(char_counters.into_iter(), total_count)
+}
+
+/// zeroth order entropy for texts over integer alphabets
+fn zero_order_entropy_of_symbols<'a, I: Iterator<Item = &'a u64>>(text_iter: I) -> f64 {
+    let mut char_counters = std::collections::HashMap::new();
+    let mut total_count = 0;
+    for c in text_iter {
+        *char_counters.entry(*c).or_insert(0_usize) += 1;
+        total_count += 1;
+    }
+    entropy_of_counts(char_counters.into_values(), total_count)
+}
+
+pub(super) fn kth_order_entropy(text: &[u8], k: usize) -> f64 {
+    let sa = {
+        let mut sa = vec![0; text.len()];
+        assert!(!text[..text.len() - 1].contains(&0));
+        cdivsufsort::sort_in_place(text, sa.as_mut_slice());
+        sa
+    };
+    kth_order_entropy_by_sa(text, &sa, k, |v| zero_order_entropy(v.iter()))
+}
+
+//@ Uses the suffix array and the LCP array to compute the kth order entropy
+//@ The idea is to partition the LCP array into blocks where each block has LCP values >= k,
+//@ then compute for each block the 0th entropy of the k-th character after each corresponding
+//@ suffix.
+fn kth_order_entropy_by_sa<C: Copy + Eq>(
+    text: &[C],
+    sa: &[i32],
+    k: usize,
+    zero_order_entropy: fn(&[C]) -> f64,
+) -> f64 {
+    assert_gt!(k, 0);
+    let lcp = {
+        let phi = core::compute_phi(sa);
+        let plcp = core::compute_plcp(text, phi.as_slice());
+        core::compute_lcp(plcp.as_slice(), sa)
+    };
+
+    let compute_context = |start: usize, length: usize| -> f64 {
+        let mut v: Vec<C> = Vec::with_capacity(length);
+        for savalue in sa.iter().skip(start).take(length) {
+            let pos = *savalue as usize + k;
+            if pos < text.len() {
+                //@ for binary texts having 0 byte the 0 byte at the end can be matched with it!
+                v.push(text[pos]);
+            }
+        }
+        (length as f64) * zero_order_entropy(&v)
+    };
+
+    let mut sum = 0 as f64;
+    let mut contextcount: usize = 0;
+    for (lcpindex, lcpvalue) in lcp.iter().enumerate() {
+        if contextcount > 0 && (*lcpvalue as usize) < k {
+            assert_gt!(lcpindex, 0);
+            sum += compute_context(lcpindex - contextcount - 1, contextcount + 1);
+            contextcount = 0;
+            continue;
+        }
+        if (*lcpvalue as usize) >= k {
+            contextcount += 1;
+        }
+    }
+    if (lcp[lcp.len() - 1] as usize) >= k && contextcount > 0 {
+        sum += compute_context(lcp.len() - contextcount - 1, contextcount + 1);
+    }
+
+    sum / (text.len() as f64)
+}
+
+#[test]
+#[allow(clippy::unnecessary_cast, clippy::legacy_numeric_constants)]
+fn test_entropy() {
+    assert_eq!(zero_order_entropy(b"aaaaa".iter()), 0.0);
+    assert_eq!(zero_order_entropy(b"bbbb".iter()), 0.0);
+    assert_eq!(
+        zero_order_entropy(b"abab".iter()),
+        zero_order_entropy(b"aabb".iter())
+    );
+    assert_eq!(
+        zero_order_entropy(b"ab".iter()),
+        zero_order_entropy(b"aabb".iter())
+    );
+
+    //@ check entropy for unary strings
+    for n in 40..45 {
+        let text = {
+            let mut text = vec![b'a'; n];
+            text[n - 1] = 0u8;
+            text
+        };
+        assert_eq!(text.len(), n);
+        for k in 1..16 {
+            let kf = k as f64;
+            let nf = n as f64;
+            let expected_entropy = ((nf - 1.0 - kf as f64) * ((nf - kf) / (nf - kf - 1.0)).log2()
+                + (nf - kf).log2())
+                / nf;
+            assert_abs_diff_eq!(
+                kth_order_entropy(text.as_slice(), k),
+                expected_entropy,
+                epsilon = std::f64::EPSILON
+            );
+        }
+    }
+    for ab_run in 8..20 {
+        let text = {
+            let mut text = vec![b'a'; ab_run * 2 + 1];
+            for i in 0..ab_run {
+                text[2 * i + 1] = b'b';
+            }
+            assert_eq!((text.len() - 1) % 2, 0);
+            let n = text.len();
+            text[n - 1] = 0u8;
+            text
+        };
+        for k in 1..5 {
+            let freq = ab_run as f64 - ((k as f64) / 2.).ceil();
+            let expected_entropy =
+                (freq * ((freq + 1.) / freq).log2() + (freq + 1.).log2()) / (text.len() as f64);
+            assert_abs_diff_eq!(
+                kth_order_entropy(text.as_slice(), k),
+                expected_entropy,
+                epsilon = std::f64::EPSILON
+            );
+        }
+    }
+}
+
+/// the entropies do not depend on the symbol values
+#[test]
+fn test_entropy_of_symbols() {
+    for text in core::RandomStringGenerator::new(0..512, 2) {
+        if text.len() < 2 {
+            continue;
+        }
+        let symbols: Vec<u64> = text.iter().map(|&c| 1 << (8 * c)).collect();
+        assert_abs_diff_eq!(
+            zero_order_entropy_of_symbols(symbols.iter()),
+            zero_order_entropy(text.iter()),
+            epsilon = 1e-9
+        );
+        let sa = core::suffixarray_prefix_doubling(&symbols).unwrap();
+        for k in 1..4 {
+            assert_abs_diff_eq!(
+                kth_order_entropy_by_sa(&symbols, &sa, k, |v| zero_order_entropy_of_symbols(
+                    v.iter()
+                )),
+                kth_order_entropy(&text, k),
+                epsilon = 1e-9
+            );
+        }
+    }
+}
+
+/// computes the empirical entropy of a given order
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the order of the entropy
+    #[arg(short = 'k', long, default_value_t = 0)]
+    order: usize,
+
+    /// the number of bytes per symbol, read in little-endian order (1, 2, 4 or 8)
+    #[arg(short = 'w', long, default_value_t = 1)]
+    symbol_width: usize,
+
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+}
+
+/// the options of the former binary, where `-o` is the order instead of the output file
+pub fn legacy_options(command: clap::Command) -> clap::Command {
+    command
+        .mut_arg("outfilename", |arg| arg.short(None))
+        .mut_arg("order", |arg| arg.short('o').visible_short_alias('k'))
+}
+
+/// computes the empirical entropy of the input of `job`
+fn run_job(args: &Args, job: &batch::Job) -> Result<Vec<report::ResultRecord>> {
+    info!("input_filename: {}", core::get_filename(&job.infilename));
+    info!("prefix_length: {}", job.prefixlength);
+
+    use std::time::Instant;
+    let now = Instant::now();
+
+    info!("read text");
+
+    //@ the length of the input, without the appended sentinel
+    let (length, h0) = if args.symbol_width == 1 {
+        let text = {
+            let mut text = io::file_or_stdin2byte_vector(
+                core::stringopt_stropt(&job.infilename),
+                job.prefixlength,
+            )?;
+            text.push(0u8);
+            text
+        };
+
+        info!("compute entropy");
+        let h0 = if args.order == 0 {
+            zero_order_entropy(text.iter())
+        } else {
+            kth_order_entropy(&text, args.order)
+        };
+        (text.len() - 1, h0)
+    } else {
+        let text = core::integer_text_with_sentinel(&io::file_or_stdin2symbol_vector(
+            core::stringopt_stropt(&job.infilename),
+            job.prefixlength,
+            args.symbol_width,
+        )?)?;
+
+        info!("compute entropy");
+        let h0 = if args.order == 0 {
+            zero_order_entropy_of_symbols(text.iter())
+        } else {
+            let sa = core::suffixarray_prefix_doubling(&text)?;
+            kth_order_entropy_by_sa(&text, &sa, args.order, |v| {
+                zero_order_entropy_of_symbols(v.iter())
+            })
+        };
+        (text.len() - 1, h0)
+    };
+
+    Ok(vec![report::ResultRecord::new(
+        "entropy",
+        "count_entropy",
+        core::get_filename(&job.infilename),
+        length,
+    )
+    .with("order", args.order)
+    .with("entropy", h0)
+    .timed(&now)])
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(args, job)
+    })
+}
@@ -0,0 +1,85 @@
+use super::GlobalArgs;
+use crate::core;
+use crate::error::{Error, Result};
+use crate::io;
+
+/// enumerates all strings over the alphabet starting at 'a' in lexicographic order, shorter strings first
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the number of letters (1..=26)
+    #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=26))]
+    sigma: u8,
+
+    /// the minimum length of the strings (at least 1)
+    #[arg(short, long, value_parser = clap::value_parser!(u64).range(1..))]
+    length: u64,
+
+    /// the maximum length of the strings (by default the minimum length)
+    #[arg(short, long)]
+    max_length: Option<u64>,
+}
+
+/// the arguments of the former binary: the number of letters, the minimum length, and optionally
+/// the maximum length, given positionally
+pub fn legacy_options(command: clap::Command) -> clap::Command {
+    ["sigma", "length", "max_length"]
+        .iter()
+        .zip(1..)
+        .fold(command, |command, (&id, index)| {
+            command.mut_arg(id, |arg| arg.short(None).long(None).index(index))
+        })
+}
+
+fn enumerate_for_length<W: std::io::Write>(
+    writer: &mut W,
+    alpha_size: usize,
+    len: usize,
+) -> std::io::Result<()> {
+    if len == 0 {
+        return Ok(());
+    }
+    // indices represent a number in base `alpha_size`, least-significant at the end
+    let mut indices = vec![0usize; len];
+    loop {
+        // build string
+        let mut s = String::with_capacity(len);
+        for &i in &indices {
+            s.push((b'a' + (i as u8)) as char);
+        }
+        writeln!(writer, "{}", s)?;
+
+        // increment
+        let mut pos = len as isize - 1;
+        while pos >= 0 {
+            if indices[pos as usize] + 1 < alpha_size {
+                indices[pos as usize] += 1;
+                break;
+            } else {
+                indices[pos as usize] = 0;
+                pos -= 1;
+            }
+        }
+        if pos < 0 {
+            break;
+        } // overflow -> finished
+    }
+    Ok(())
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    global.check_no_prefixlength("enumerate")?;
+    let min_len = args.length as usize;
+    let max_len = args.max_length.map_or(min_len, |len| len as usize);
+    if max_len < min_len {
+        return Err(Error::InvalidArgument(format!(
+            "the maximum length {} is smaller than the minimum length {}",
+            max_len, min_len
+        )));
+    }
+
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+    for len in min_len..=max_len {
+        enumerate_for_length(&mut writer, args.sigma as usize, len)?;
+    }
+    Ok(())
+}
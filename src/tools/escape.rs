@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use log::info;
+
+use super::GlobalArgs;
+use crate::core;
+use crate::error::{Error, Result};
+use crate::io;
+
+// use std::io::prelude::*;
+
+/// escapes byte sequences
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the escape symbol
+    #[arg(short, long, default_value_t = 0)]
+    escape_symbol: u8,
+
+    /// byte codes that need to be escaped, a list separeted by commas
+    #[arg(short, long)]
+    from_symbols: Vec<u8>,
+
+    /// list byte codes that are safe and equal of length to from
+    #[arg(short, long)]
+    to_symbols: Vec<u8>,
+
+    /// if true, invert the conversion
+    #[arg(long)]
+    is_reversion: bool,
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    // let matches = clap_app!(escape =>
+    //     (about: "escape byte sequences")
+    //     (@arg revert: -r --revert  "unescapes")
+    //     (@arg escape: -e --escape  +takes_value  +required "the escape symbol")
+    //     (@arg from:   -f --from    +takes_value +required "byte codes that need to be escaped, a list separeted by commas")
+    //     (@arg to:     -t --to      +takes_value  +required "list byte codes that are safe and equal of length to from")
+    //     (@arg prefix: -p --prefix  +takes_value "optional: the length of the prefix to parse")
+    //     (@arg input:  -i --infile  +takes_value "optional: the input file to read (otherwise read from stdin")
+    //     (@arg output: -o --outfile +takes_value "optional: the output file to write (otherwise write from stdout")
+    // ).get_matches();
+    //
+    //
+    // let global.prefixlength = {
+    //     let global.prefixlength = matches.value_of("prefix").unwrap_or("0").parse::<usize>().unwrap();
+    //     if global.prefixlength == 0 { std::usize::MAX } else { global.prefixlength }
+    // };
+    // let escape_symbol : u8 = matches.value_of("escape").unwrap_or("0").parse::<u8>().unwrap();
+    // let from_symbols : Vec<u8> = matches.value_of("from").unwrap().split(",").map(|s| -> u8 { s.parse::<u8>().unwrap()  }).collect();
+    // let to_symbols : Vec<u8> = matches.value_of("to").unwrap().split(",").map(|s| -> u8 { s.parse::<u8>().unwrap()  }).collect();
+    // let is_reversion = matches.is_present("revert");
+    let reader = io::stream_or_stdin(core::stringopt_stropt(&global.infilename))?;
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+
+    //@ sanity checks
+    let invalid_argument = |message: &str| Err(Error::InvalidArgument(message.to_string()));
+    if args.from_symbols.len() != args.to_symbols.len() {
+        return invalid_argument("the sets of --from and --to symbols must be of equal size");
+    }
+    if args.from_symbols.contains(&args.escape_symbol) {
+        return invalid_argument("the set of --from symbols must not contain the escape symbol");
+    }
+    if args.to_symbols.contains(&args.escape_symbol) {
+        return invalid_argument("the set of --to symbols must not contain the escape symbol");
+    }
+    if args
+        .from_symbols
+        .iter()
+        .any(|&i| args.to_symbols.contains(&i))
+    {
+        return invalid_argument("the sets of --from and --to symbols must be disjoint");
+    }
+
+    info!("global.prefixlength: {}", global.prefixlength);
+
+    //@ a prefix length of 0 means to read the entire input
+    let prefix_length = if global.prefixlength == 0 {
+        u64::MAX
+    } else {
+        global.prefixlength as u64
+    };
+    let mut bytes = io::ByteReader::new(std::io::Read::take(reader, prefix_length));
+
+    if args.is_reversion {
+        let revert_mapping = {
+            let mut revert_mapping = HashMap::new();
+            for i in 0..args.from_symbols.len() {
+                revert_mapping.insert(args.to_symbols[i], args.from_symbols[i]);
+            }
+            revert_mapping
+        };
+        while let Some(cur_char) = bytes.next_byte()? {
+            if cur_char == args.escape_symbol {
+                let next_char = bytes.next_byte()?.ok_or_else(|| {
+                    Error::InvalidInput(String::from("the input ends with an escape symbol"))
+                })?;
+                if next_char == args.escape_symbol {
+                    writer.write_all(&[args.escape_symbol])?;
+                    continue;
+                }
+                let original_char = revert_mapping.get(&next_char).ok_or_else(|| {
+                    Error::InvalidInput(format!(
+                        "the escape symbol is followed by the unescaped byte {}",
+                        next_char
+                    ))
+                })?;
+                writer.write_all(&[*original_char])?;
+            } else {
+                writer.write_all(&[cur_char])?;
+            }
+        }
+    } else {
+        let char_mapping = {
+            let mut char_mapping = HashMap::new();
+            for i in 0..args.from_symbols.len() {
+                char_mapping.insert(args.from_symbols[i], args.to_symbols[i]);
+            }
+            char_mapping
+        };
+        for cur_char in bytes {
+            let cur_char = cur_char?;
+            if cur_char == args.escape_symbol {
+                writer.write_all(&[args.escape_symbol, args.escape_symbol])?;
+                continue;
+            }
+            match char_mapping.get(&cur_char) {
+                Some(remapped_char) => writer.write_all(&[args.escape_symbol, *remapped_char]),
+                None => writer.write_all(&[cur_char]),
+            }?;
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
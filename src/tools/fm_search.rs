@@ -0,0 +1,136 @@
+use log::info;
+
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::fmindex::{ByteRank, ByteWaveletTree, FMIndex, SampledOccurrences};
+use crate::io;
+use crate::report;
+use crate::waveletmatrix::WaveletMatrix;
+
+/// builds the FM-index of a text and searches for patterns given line by line in a file
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the file containing the patterns, one per line
+    #[arg(short = 'f', long)]
+    patternfilename: String,
+
+    /// sample every s-th text position of the suffix array
+    #[arg(short, long, default_value_t = 32)]
+    sample_rate: usize,
+
+    /// also locate the occurrences, not only count them
+    #[arg(short, long)]
+    locate: bool,
+
+    /// use a wavelet tree instead of sampled occurrence counts for rank on the BWT
+    #[arg(short, long)]
+    wavelet_tree: bool,
+
+    /// use a wavelet matrix instead of sampled occurrence counts for rank on the BWT
+    #[arg(short = 'm', long, conflicts_with = "wavelet_tree")]
+    wavelet_matrix: bool,
+
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+}
+
+/// builds the FM-index of `text` with rank support `R` and searches for `patterns`
+fn search<R: ByteRank>(
+    global: &GlobalArgs,
+    args: &Args,
+    job: &batch::Job,
+    text: &[u8],
+    patterns: &[&[u8]],
+) -> Result<report::ResultRecord> {
+    use std::time::Instant;
+    let now = Instant::now();
+
+    info!("build FM-index");
+    let index = FMIndex::<R>::new(text, args.sample_rate)?;
+    let construction_time = now.elapsed().as_millis();
+    let bwt_runs = {
+        let bwt: Vec<u8> = (0..index.len()).map(|i| index.bwt_access(i)).collect();
+        core::number_of_runs_in(&bwt)
+    };
+
+    info!("search patterns");
+    let now = Instant::now();
+    let mut writer = match &global.outfilename {
+        Some(filename) => Some(io::stream_or_stdout(Some(filename))?),
+        None => None,
+    };
+    let mut occurrences = 0;
+    for pattern in patterns {
+        if args.locate {
+            let positions = index.locate(pattern);
+            occurrences += positions.len();
+            if let Some(writer) = writer.as_mut() {
+                writeln!(writer, "{} {:?}", positions.len(), positions)?;
+            }
+        } else {
+            let count = index.count(pattern);
+            occurrences += count;
+            if let Some(writer) = writer.as_mut() {
+                writeln!(writer, "{}", count)?;
+            }
+        }
+    }
+
+    //@ time_ms is the query time
+    Ok(report::ResultRecord::new(
+        "fm_search",
+        "fm_index",
+        core::get_filename(&job.infilename),
+        text.len(),
+    )
+    .with("construction_time_ms", construction_time as u64)
+    .with("bwt_runs", bwt_runs)
+    .with("index_bytes", index.size_in_bytes())
+    .with("sample_rate", args.sample_rate)
+    .with("wavelet_tree", args.wavelet_tree)
+    .with("wavelet_matrix", args.wavelet_matrix)
+    .with("locate", args.locate)
+    .with("patterns", patterns.len())
+    .with("occurrences", occurrences)
+    .timed(&now))
+}
+
+/// searches the patterns in the input of `job`
+fn run_job(
+    global: &GlobalArgs,
+    args: &Args,
+    job: &batch::Job,
+    patterns: &[&[u8]],
+) -> Result<Vec<report::ResultRecord>> {
+    info!("filename: {}", core::get_filename(&job.infilename));
+    info!("prefixlength: {}", job.prefixlength);
+
+    info!("read text");
+    let text =
+        io::file_or_stdin2mapped_bytes(core::stringopt_stropt(&job.infilename), job.prefixlength)?;
+
+    let record = if args.wavelet_tree {
+        search::<ByteWaveletTree>(global, args, job, &text, patterns)?
+    } else if args.wavelet_matrix {
+        search::<WaveletMatrix>(global, args, job, &text, patterns)?
+    } else {
+        search::<SampledOccurrences>(global, args, job, &text, patterns)?
+    };
+    Ok(vec![record])
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    info!("read patterns");
+    let pattern_text = io::file2mapped_bytes(&args.patternfilename, 0)?;
+    let patterns: Vec<&[u8]> = io::lines(&pattern_text)
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::check_single_output(&jobs, &global.outfilename)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(global, args, job, &patterns)
+    })
+}
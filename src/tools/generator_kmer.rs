@@ -0,0 +1,44 @@
+use generator::{done, Gn};
+use std::io::Write;
+use std::str;
+
+use super::GlobalArgs;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+
+/// enumerates all strings of a given length over the alphabet starting at 'a'
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the length of the strings
+    #[arg(short, long, default_value_t = 8)]
+    length: usize,
+
+    /// the number of letters
+    #[arg(short, long, default_value_t = 4, value_parser = clap::value_parser!(u8).range(1..=26))]
+    sigma: u8,
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    global.check_no_prefixlength("generator_kmer")?;
+    let length = args.length;
+    let alphabet_size = args.sigma as usize;
+    let g = Gn::new_scoped(|mut s| {
+        for num in 0..usize::pow(alphabet_size, length as u32) {
+            let mut text = vec![b'a'; length];
+            let mut remainder = num;
+            for letter in &mut text {
+                *letter = b'a' + ((remainder % alphabet_size) as u8);
+                remainder /= alphabet_size;
+            }
+            s.yield_(text);
+        }
+        done!();
+    });
+
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+    for i in g {
+        writeln!(writer, "{}", str::from_utf8(i.as_slice()).unwrap())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,353 @@
+use log::info;
+use more_asserts::{assert_gt, assert_lt};
+
+use super::GlobalArgs;
+use crate::core;
+use crate::error::{Error, Result};
+use crate::io;
+use crate::report;
+
+use std::cell::RefCell;
+
+/// represents a suffix array node as as an interval in the SA/LCP array
+#[derive(Debug, Clone)]
+struct LCPInterval {
+    /// string depth of the node
+    depth: u32,
+    /// first suffix array position
+    begin: u32,
+    /// last suffix array position
+    end: u32,
+}
+use std::rc::Rc;
+
+/// represents an edge in the suffix tree
+#[derive(Debug)]
+struct SuffixEdge {
+    parent: Rc<RefCell<LCPInterval>>,
+    #[allow(dead_code)]
+    label: u8,
+    child: Rc<RefCell<LCPInterval>>,
+}
+
+fn lcp_intervals(text: &[u8], sa: &[i32], lcp: &[u32]) -> Vec<SuffixEdge> {
+    let n = text.len();
+    // let root = LCPInterval { depth : 0, begin : 0, end: (n-1) as u32};
+    // let mut leaf = LCPInterval { depth : n as u32 - (sa[0] as u32), begin : 0, end : 0 };
+    // let path = vec![&root, &leaf];
+
+    let mut lcpintervals = vec![
+        Rc::new(RefCell::new(LCPInterval {
+            depth: 0,
+            begin: 0,
+            end: (n - 1) as u32,
+        })),
+        Rc::new(RefCell::new(LCPInterval {
+            depth: n as u32 - (sa[0] as u32) - 1,
+            begin: 0,
+            end: 0,
+        })),
+    ];
+    let mut path = vec![Rc::clone(&lcpintervals[0]), Rc::clone(&lcpintervals[1])];
+    // let mut leaf = lcpintervals.last().unwrap();
+
+    let mut edges = Vec::new();
+
+    for i in 1..n {
+        let mut child_ptr: Option<Rc<RefCell<LCPInterval>>> = None;
+        while lcp[i] < path.last().unwrap().borrow().depth {
+            let node = Rc::clone(&path.pop().unwrap());
+            let mut node_ref = (*node).borrow_mut();
+            node_ref.end = (i - 1) as u32;
+
+            if let Some(child) = &child_ptr {
+                let label =
+                    text[sa[child.borrow().begin as usize] as usize + node_ref.depth as usize];
+                edges.push(SuffixEdge {
+                    parent: Rc::clone(&node),
+                    label,
+                    child: Rc::clone(child),
+                });
+            }
+            child_ptr = Some(Rc::clone(&node));
+        }
+        if lcp[i] > path.last().unwrap().borrow().depth {
+            assert!(child_ptr.is_some());
+            // create internal node
+            if let Some(child) = &child_ptr {
+                let child_begin = child.borrow().begin;
+                assert_lt!(lcp[i] as usize + sa[child_begin as usize] as usize, n);
+                lcpintervals.push(Rc::new(RefCell::new(LCPInterval {
+                    depth: lcp[i],
+                    begin: child_begin,
+                    end: n as u32,
+                })));
+                path.push(Rc::clone(lcpintervals.last().unwrap()));
+            }
+        }
+        if let Some(child_rc) = child_ptr {
+            let child = Rc::clone(&child_rc);
+            let label = text[sa[child.borrow().begin as usize] as usize
+                + path.last().unwrap().borrow().depth as usize];
+            edges.push(SuffixEdge {
+                parent: Rc::clone(path.last().unwrap()),
+                label,
+                child,
+            });
+        }
+        // create a new leaf for index i
+        assert_lt!(sa[i] as u32 + n as u32 - (sa[i] as u32) - 1, n as u32);
+        lcpintervals.push(Rc::new(RefCell::new(LCPInterval {
+            depth: n as u32 - (sa[i] as u32) - 1,
+            begin: i as u32,
+            end: i as u32,
+        })));
+        path.push(Rc::clone(lcpintervals.last().unwrap()));
+    }
+    let mut child_ptr: Option<Rc<RefCell<LCPInterval>>> = None;
+    // treat remaining nodes on `path`
+    while let Some(node) = path.pop() {
+        node.borrow_mut().end = (n - 1) as u32;
+        if let Some(child) = &child_ptr {
+            let label =
+                text[sa[child.borrow().begin as usize] as usize + node.borrow().depth as usize];
+            edges.push(SuffixEdge {
+                parent: Rc::clone(&node),
+                label,
+                child: Rc::clone(child),
+            });
+        }
+        child_ptr = Some(Rc::clone(&node));
+    }
+    // let mut ret = Vec::new();
+
+    #[cfg(debug_assertions)]
+    for interval in lcpintervals {
+        let interval = (*(*interval).borrow()).clone();
+        // ret.push((*(*interval).borrow()).clone());
+        // ret.push(*RefCell::as_ptr(interval));
+        assert_lt!(interval.begin as usize, n);
+        assert_lt!(interval.end as usize, n);
+        assert_lt!(
+            sa[interval.begin as usize] as usize + interval.depth as usize,
+            n
+        );
+    }
+
+    edges
+}
+
+/// checks whether the given text positions form a string attractor of the text
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// string attractor
+    #[arg(short, long,num_args(1..))]
+    attractor: Vec<u64>,
+}
+
+fn is_attractor(text: &[u8], attractor: &[u64]) -> bool {
+    assert_gt!(text.len(), 0);
+    assert_eq!(*text.last().unwrap(), 0u8);
+    let n = text.len();
+    for &attractor_position in attractor {
+        assert_lt!(attractor_position as usize, n);
+    }
+
+    let sa = {
+        let mut sa = vec![0; text.len()];
+        cdivsufsort::sort_in_place(text, sa.as_mut_slice());
+        sa
+    };
+    // let isa = core::inverse_permutation(&sa.as_slice());
+    let lcp = {
+        let phi = core::compute_phi(sa.as_slice());
+        let plcp = core::compute_plcp(text, phi.as_slice());
+        core::compute_lcp(plcp.as_slice(), sa.as_slice())
+    };
+    let suffix_edges = lcp_intervals(text, &sa, &lcp);
+
+    // use succinct::BitVector;
+    use succinct::*;
+    // use succinct::Select1Support;
+    // use succinct::bit_vec::BitVecMut;
+    // use succinct::BinSearchSelect;
+
+    let attractor_positions = {
+        let mut v = BitVector::with_fill(n as u64, false);
+        for s in attractor.iter() {
+            v.set_bit(*s, true);
+        }
+        v
+    };
+    // use succinct::Rank9;
+    //TODO: remove clone() calls!
+    let rank = Rank9::new(attractor_positions.clone());
+    let select = BinSearchSelect::new(rank.clone()); //@ starts with index 0
+
+    // for i in 0..n {
+    //     println!("rank {} -> {}", i, rank.rank1(i as u64));
+    //     if let Some(pos) = select.select1(i as u64) {
+    //         println!("select {} -> {:?}",i,  pos);
+    //     }
+    // }
+
+    let mut arr_d = vec![0; n];
+    for i in 0..n {
+        let text_position = sa[i] as u64;
+        if !attractor_positions.get_bit(text_position) {
+            let successor_rank = rank.rank1(text_position);
+            match select.select1(successor_rank) {
+                Some(pos) => {
+                    assert!(attractor_positions.get_bit(pos));
+                    arr_d[i] = pos - text_position;
+                }
+                None => arr_d[i] = n as u64,
+            }
+        }
+    }
+
+    use crate::rmq::{RangeMinimumQuery, SuccinctRMQ};
+    let d_rmq = SuccinctRMQ::new(&arr_d);
+
+    let mut is_attractor = true;
+    for edge in suffix_edges {
+        let lcplength = edge.parent.borrow().depth as usize + 1;
+        let lcpinterval = edge.child.borrow();
+        info!("{:?}", lcpinterval);
+        let rmq = d_rmq.query(lcpinterval.begin as usize, lcpinterval.end as usize + 1) as usize;
+        if lcplength <= rmq {
+            is_attractor = false;
+            let startpos = sa[lcpinterval.begin as usize] as usize;
+            let endpos = std::cmp::min(sa[lcpinterval.begin as usize] as usize + lcplength, n);
+            info!(
+                "substring '{}' not covered",
+                String::from_utf8_lossy(&text[startpos..endpos])
+            );
+        }
+    }
+    is_attractor
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    info!("prefixlength: {}", global.prefixlength);
+    let now = std::time::Instant::now();
+
+    let text = {
+        let mut text = io::file_or_stdin2byte_vector(
+            core::stringopt_stropt(&global.infilename),
+            global.prefixlength,
+        )?;
+        //@ the zero byte is reserved for the sentinel building the LCP intervals
+        if text.contains(&0) {
+            return Err(Error::InvalidInput(String::from(
+                "the input text contains bytes equal to zero",
+            )));
+        }
+        text.push(0u8);
+        text
+    };
+    let n = text.len();
+
+    for attractor_position in args.attractor.as_slice() {
+        if (*attractor_position as usize) >= n {
+            return Err(Error::InvalidArgument(format!(
+                "specified attractor position {} is larger than text (length: {})",
+                *attractor_position, n
+            )));
+        }
+    }
+
+    let is_attractor = is_attractor(text.as_slice(), args.attractor.as_slice());
+    let record = report::ResultRecord::new(
+        "is_stringattractor",
+        "lcp_intervals",
+        core::get_filename(&global.infilename),
+        n - 1, //@ the length of the input, without the sentinel
+    )
+    .with("attractor_size", args.attractor.len())
+    .with("is_attractor", is_attractor)
+    .timed(&now);
+    report::Reporter::new(global.format).emit(record);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word;
+
+    /// Period Doubling Sequences have a string attractor of length 2.
+    /// Ref:
+    /// Luke Schaeffer, Jeffrey Shallit
+    /// String Attractors for Automatic Sequences. CoRR abs/2012.06840 (2020), https://arxiv.org/abs/2012.06840
+    #[test]
+    fn test_attractor_period_doubling() {
+        for i in 5..16 {
+            let mut text = word::period_doubling_word(i - 1);
+            let attractor = [3 * (1 << (i - 4)) - 1, 3 * (1 << (i - 3)) - 1];
+            text.push(0u8);
+            assert!(is_attractor(text.as_slice(), &attractor));
+        }
+    }
+
+    /// The minimum string attractor of Sturmian words has been identified to be exactly of size two,
+    /// where the positions are chosen directly at the boundary of the two previous recurrent
+    /// substrings.
+    /// Ref:
+    /// Sabrina Mantaci, Antonio Restivo, Giuseppe Romana, Giovanna Rosone, Marinella Sciortino:
+    /// String Attractors and Combinatorics on Words. ICTCS 2019: 57-71, http://ceur-ws.org/Vol-2504/paper8.pdf
+    #[test]
+    fn test_attractor_fibonacci_attractor() {
+        for i in 3..16 {
+            let attractor = [
+                word::fibonacci_number(i - 1) as u64 - 1,
+                word::fibonacci_number(i - 1) as u64 - 2,
+            ];
+            let mut text = word::fibonacci_word(i);
+            text.push(0u8);
+            assert!(is_attractor(text.as_slice(), &attractor));
+        }
+    }
+
+    /// The palindromic prefixes of an Arnoux-Rauzy word over k letters have a string attractor
+    /// of size k. As attractor, we take for each letter x the position |u_{m-1}|, where m is
+    /// the last index of x in the directive sequence, such that u_m is the palindromic closure
+    /// of u_{m-1} x.
+    /// Ref:
+    /// Antonio Restivo, Giuseppe Romana, Marinella Sciortino:
+    /// String Attractors and Infinite Words. LATIN 2022: 426-442
+    #[test]
+    fn test_attractor_arnoux_rauzy() {
+        for (directive, sigma) in [
+            (b"abababab".to_vec(), 2),
+            (b"aabbbab".to_vec(), 2),
+            (b"abcabcabcabc".to_vec(), 3),
+            (b"aabbccabacbcab".to_vec(), 3),
+            (b"abcdabcdabcd".to_vec(), 4),
+            (b"abacadbcdbcaddcab".to_vec(), 4),
+        ] {
+            for length in sigma..=directive.len() {
+                let directive = &directive[..length];
+                let Ok(mut text) = word::arnoux_rauzy_word(directive, sigma) else {
+                    continue; //@ not all letters occur yet
+                };
+                let mut attractor: Vec<u64> = (0..sigma as u8)
+                    .map(|rank| {
+                        let last = directive.iter().rposition(|&c| c == b'a' + rank).unwrap();
+                        word::episturmian_word(&directive[..last]).len() as u64
+                    })
+                    .collect();
+                attractor.sort();
+                text.push(0u8);
+                assert!(
+                    is_attractor(text.as_slice(), &attractor),
+                    "{:?} {:?}",
+                    String::from_utf8_lossy(directive),
+                    attractor
+                );
+                //@ each letter needs its own position
+                assert!(!is_attractor(text.as_slice(), &attractor[1..]));
+            }
+        }
+    }
+}
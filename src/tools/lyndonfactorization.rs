@@ -0,0 +1,153 @@
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+
+use log::{debug, info, log_enabled, Level};
+
+use std::time::Instant;
+/// computes the Lyndon factors with Duval's algorithm
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the number of bytes per symbol, read in little-endian order (1, 2, 4 or 8)
+    #[arg(short = 'w', long, default_value_t = 1)]
+    symbol_width: usize,
+
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+
+    #[command(flatten)]
+    sweep: batch::SweepArgs,
+}
+
+/// writes the Lyndon factors of `text` ending at the positions `factors`, each symbol in
+/// `symbol_width` bytes
+fn write_factors<W: std::io::Write, C: Copy + Into<u64>>(
+    os: &mut W,
+    text: &[C],
+    factors: &[usize],
+    symbol_width: usize,
+) -> std::io::Result<()> {
+    os.write_all(b">Factor 1\n")?;
+    io::write_symbols(os, &text[0..factors[0] + 1], symbol_width)?;
+    for factor_id in 1..factors.len() {
+        // do not print the last NULL byte factor if such a factor exists
+        if factor_id == factors.len() - 1
+            && factors[factor_id] + 1 == text.len()
+            && text[text.len() - 1].into() == 0
+        {
+            break;
+        }
+        info!(
+            "writing Factor {} : {} -> {}",
+            factor_id + 1,
+            factors[factor_id - 1] + 1,
+            factors[factor_id] + 1
+        );
+        write!(
+            os,
+            "\n>Factor {} : {} -> {}\n",
+            factor_id + 1,
+            factors[factor_id - 1] + 1,
+            factors[factor_id] + 1
+        )?;
+        io::write_symbols(
+            os,
+            &text[factors[factor_id - 1] + 1..factors[factor_id] + 1],
+            symbol_width,
+        )?;
+    }
+    Ok(())
+}
+
+/// computes the Lyndon factorization of `text`, writes it to the output file if given, and
+/// returns the result records
+fn factorize<C: Ord + Copy + Into<u64>>(
+    text: &[C],
+    global: &GlobalArgs,
+    args: &Args,
+    job: &batch::Job,
+) -> Result<Vec<report::ResultRecord>> {
+    let timenow = Instant::now();
+    let result = |length: usize, factors: usize| {
+        report::ResultRecord::new(
+            "lyndonfactorization",
+            "duval",
+            core::get_filename(&job.infilename),
+            length,
+        )
+        .with("factors", factors)
+        .timed(&timenow)
+    };
+
+    let factors = core::duval(text);
+    assert_eq!(factors.last().map_or(0, |&last| last + 1), text.len());
+
+    if log_enabled!(Level::Debug) {
+        debug!("Lyndon factorization : {:?}", factors);
+    }
+
+    let records = if args.sweep.is_enabled() {
+        let steps = args.sweep.steps(text.len())?;
+        let counts = core::lyndon_factor_counts_of_prefixes(text, &steps);
+        steps
+            .iter()
+            .zip(counts)
+            .map(|(&length, count)| result(length, count))
+            .collect()
+    } else {
+        vec![result(text.len(), factors.len())]
+    };
+
+    if let Some(output_filename) = &global.outfilename {
+        let mut os = io::stream_or_stdout(Some(output_filename))?;
+        write_factors(&mut os, text, &factors, args.symbol_width)?;
+    }
+
+    if log_enabled!(Level::Debug) {
+        #[cfg(debug_assertions)]
+        {
+            let sa = core::suffixarray_prefix_doubling(text)?;
+            let isa = core::inverse_permutation(sa.as_slice());
+            debug_assert_eq!(factors, core::isa_lyndon_factorization(&isa));
+        }
+    }
+    Ok(records)
+}
+
+/// computes the Lyndon factorization of the input of `job`
+fn run_job(
+    global: &GlobalArgs,
+    args: &Args,
+    job: &batch::Job,
+) -> Result<Vec<report::ResultRecord>> {
+    info!("filename: {}", core::get_filename(&job.infilename));
+    info!("prefixlength: {}", job.prefixlength);
+
+    info!("read text");
+
+    if args.symbol_width == 1 {
+        let text = io::file_or_stdin2mapped_bytes(
+            core::stringopt_stropt(&job.infilename),
+            job.prefixlength,
+        )?;
+        factorize(&text, global, args, job)
+    } else {
+        let text = io::file_or_stdin2symbol_vector(
+            core::stringopt_stropt(&job.infilename),
+            job.prefixlength,
+            args.symbol_width,
+        )?;
+        factorize(&text, global, args, job)
+    }
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::check_single_output(&jobs, &global.outfilename)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(global, args, job)
+    })
+}
@@ -0,0 +1,114 @@
+use super::GlobalArgs;
+use crate::core;
+use crate::error::{Error, Result};
+use crate::io;
+use crate::necklace::{self, NecklaceKind};
+
+/// enumerates all Lyndon words up to a given length in lexicographic order, or the necklaces,
+/// prenecklaces, bracelets or Lyndon words of the given length, and counts, ranks or unranks them
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the maximum length of the Lyndon words to generate, or the length of the words of the given kind
+    #[arg(short, long, default_value_t = 5)]
+    length: usize,
+
+    /// the alphabet size of the generated Lyndon words
+    #[arg(short, long, default_value_t = 2)]
+    sigma: usize,
+
+    /// enumerate the words of this kind of exactly the given length
+    #[arg(short, long, value_enum)]
+    kind: Option<NecklaceKind>,
+
+    /// output the numbers of prenecklaces, necklaces, Lyndon words and bracelets of the given length
+    #[arg(short, long, conflicts_with_all = ["kind", "rank", "unrank"])]
+    count: bool,
+
+    /// output the rank (starting with 0) of the given word among the Lyndon words, or with --kind necklace among the necklaces, of its length
+    #[arg(long, conflicts_with = "unrank")]
+    rank: Option<String>,
+
+    /// output the Lyndon word, or with --kind necklace the necklace, of the given length with this rank
+    #[arg(long)]
+    unrank: Option<u128>,
+}
+
+/// whether to rank among necklaces instead of Lyndon words
+fn ranks_necklaces(kind: Option<NecklaceKind>) -> Result<bool> {
+    match kind {
+        None | Some(NecklaceKind::Lyndon) => Ok(false),
+        Some(NecklaceKind::Necklace) => Ok(true),
+        Some(kind) => Err(Error::InvalidArgument(format!(
+            "only Lyndon words and necklaces can be ranked, not the kind {}",
+            clap::ValueEnum::to_possible_value(&kind)
+                .unwrap()
+                .get_name()
+        ))),
+    }
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    global.check_no_prefixlength("lyndonwords")?;
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+
+    if args.count {
+        let counts = [
+            (
+                "prenecklaces",
+                necklace::prenecklace_count(args.sigma, args.length)?,
+            ),
+            (
+                "necklaces",
+                necklace::necklace_count(args.sigma, args.length)?,
+            ),
+            ("lyndon", necklace::lyndon_count(args.sigma, args.length)?),
+            (
+                "bracelets",
+                necklace::bracelet_count(args.sigma, args.length)?,
+            ),
+        ];
+        for (name, count) in counts {
+            writeln!(writer, "{}\t{}", name, count)?;
+        }
+        return Ok(());
+    }
+    //@ the words are written with the letters a, b, ...
+    if !(1..=26).contains(&args.sigma) {
+        return Err(Error::InvalidArgument(format!(
+            "the alphabet size {} is not in 1..=26",
+            args.sigma
+        )));
+    }
+    if let Some(word) = &args.rank {
+        let word: Vec<u8> = word.bytes().map(|c| c.wrapping_sub(b'a')).collect();
+        let rank = if ranks_necklaces(args.kind)? {
+            necklace::necklace_rank(&word, args.sigma)?
+        } else {
+            necklace::lyndon_rank(&word, args.sigma)?
+        };
+        writeln!(writer, "{}", rank)?;
+        return Ok(());
+    }
+    let words: Box<dyn Iterator<Item = Vec<u8>>> = if let Some(rank) = args.unrank {
+        let word = if ranks_necklaces(args.kind)? {
+            necklace::necklace_unrank(rank, args.length, args.sigma)?
+        } else {
+            necklace::lyndon_unrank(rank, args.length, args.sigma)?
+        };
+        Box::new(std::iter::once(word))
+    } else if let Some(kind) = args.kind {
+        Box::new(necklace::NecklaceGenerator::new(
+            args.length,
+            args.sigma,
+            kind,
+        )?)
+    } else {
+        Box::new(core::LyndonWordGenerator::new(args.length, args.sigma))
+    };
+    for it in words {
+        let out: Vec<u8> = it.iter().map(|x| x + b'a').collect::<Vec<u8>>();
+        writer.write_all(&out)?;
+        writer.write_all(b"\n")?;
+    }
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use super::GlobalArgs;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+
+pub fn mtf<R: std::io::Read, W: std::io::Write>(
+    reader: &mut R,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let mut mtfvector: Vec<u8> = (0..=u8::MAX).collect();
+    for cur_char in io::ByteReader::new(reader) {
+        let cur_char = cur_char?;
+        let pos = mtfvector.iter().position(|&c| c == cur_char).unwrap();
+        mtfvector.copy_within(0..pos, 1);
+        mtfvector[0] = cur_char;
+        writer.write_all(&[pos as u8])?;
+    }
+    writer.flush()
+}
+
+pub fn mtf_vector(mut input: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    mtf(&mut input, &mut output).expect("reading from and writing to memory cannot fail");
+    output
+}
+
+#[cfg(test)]
+#[test]
+#[allow(
+    clippy::char_lit_as_u8,
+    clippy::unnecessary_cast,
+    clippy::unnecessary_mut_passed,
+    clippy::needless_borrow
+)]
+fn test_mtf() {
+    assert_eq!(mtf_vector(&mut b"aa".as_ref()), ['a' as u8, 0 as u8]);
+    assert_eq!(mtf_vector(&mut b"ba".as_ref()), ['b' as u8, 'a' as u8 + 1]);
+    assert_eq!(
+        mtf_vector(&mut b"abab".as_ref()),
+        ['a' as u8, 'b' as u8, 1, 1]
+    );
+    assert_eq!(
+        mtf_vector(&mut b"abba".as_ref()),
+        ['a' as u8, 'b' as u8, 0, 1]
+    );
+    assert_eq!(
+        mtf_vector(&mut b"aabb".as_ref()),
+        ['a' as u8, 0, 'b' as u8, 0]
+    );
+    assert_eq!(mtf_vector([255, 0, 255].as_ref()), [255, 1, 1]);
+}
+
+/// computes move to front encoding
+#[derive(clap::Args, Debug)]
+pub struct Args {}
+
+pub fn run(global: &GlobalArgs, _args: &Args) -> Result<()> {
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+    let mut reader = io::prefix_stream_or_stdin(
+        core::stringopt_stropt(&global.infilename),
+        global.prefixlength,
+    )?;
+    mtf(&mut reader, &mut writer)?;
+    Ok(())
+}
@@ -0,0 +1,192 @@
+use log::info;
+
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+
+/// computes the minimal unique substrings
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the number of bytes per symbol, read in little-endian order (1, 2, 4 or 8)
+    #[arg(short = 'w', long, default_value_t = 1)]
+    symbol_width: usize,
+
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+}
+
+fn get_mus(sa: &[i32], isa: &[i32], lcp: &[u32]) -> Vec<(usize, usize)> {
+    let n = sa.len() - 1;
+
+    let getlength = |i: usize| -> usize {
+        let isai = isa[i] as usize;
+        if isai + 1 == sa.len() {
+            lcp[isai] as usize
+        } else {
+            std::cmp::max(lcp[isai], lcp[isa[i] as usize + 1]) as usize
+        }
+    };
+
+    let mut mus = Vec::new();
+
+    for i in 0..n {
+        let elli = getlength(i);
+        assert!(
+            elli + i < sa.len(),
+            "cannot happen since the last character is always a sentinel"
+        );
+        if elli + i >= n {
+            continue;
+        }
+        let nextell = getlength(i + 1);
+        if elli <= nextell {
+            mus.push((i, elli + 1));
+        }
+    }
+    mus
+}
+
+/// computes the inverse suffix array and the LCP array from the suffix array `sa` of `text`
+fn build_arrays<C: Eq>(text: &[C], sa: Vec<i32>) -> (Vec<i32>, Vec<i32>, Vec<u32>) {
+    let isa = core::inverse_permutation(sa.as_slice());
+    let lcp = {
+        let phi = core::compute_phi(sa.as_slice());
+        let plcp = core::compute_plcp(text, phi.as_slice());
+        core::compute_lcp(plcp.as_slice(), sa.as_slice())
+    };
+    (sa, isa, lcp)
+}
+
+/// The inverse suffix array and the LCP array of the symbols `symbols` followed by a sentinel,
+/// where the symbols are shifted by one such that the sentinel 0 is unique.
+fn symbol_arrays(symbols: &[u64]) -> Result<(Vec<i32>, Vec<i32>, Vec<u32>)> {
+    let text = core::integer_text_with_sentinel(symbols)?;
+    let sa = core::suffixarray_prefix_doubling(&text)?;
+    Ok(build_arrays(&text, sa))
+}
+
+/// The inverse suffix array and the LCP array of the bytes `bytes` followed by a sentinel, like
+/// [`symbol_arrays`] for the bytes read as symbols.
+fn byte_arrays(bytes: &[u8]) -> Result<(Vec<i32>, Vec<i32>, Vec<u32>)> {
+    if bytes.contains(&0) {
+        //@ a zero byte would not be smaller than the sentinel, so treat the bytes as symbols
+        let symbols: Vec<u64> = bytes.iter().map(|&c| c as u64).collect();
+        return symbol_arrays(&symbols);
+    }
+    let mut text = bytes.to_vec();
+    text.push(0u8);
+    let sa = {
+        let mut sa = vec![0; text.len()];
+        cdivsufsort::sort_in_place(&text, sa.as_mut_slice());
+        sa
+    };
+    Ok(build_arrays(&text, sa))
+}
+
+/// computes the minimal unique substrings of the input of `job`
+fn run_job(args: &Args, job: &batch::Job) -> Result<Vec<report::ResultRecord>> {
+    info!("prefixlength: {}", job.prefixlength);
+
+    use std::time::Instant;
+
+    info!("Build DS");
+    let now = Instant::now();
+
+    //@ both paths append a sentinel, which get_mus expects at the end of the text
+    let (sa, isa, lcp) = if args.symbol_width == 1 {
+        byte_arrays(&io::file_or_stdin2mapped_bytes(
+            core::stringopt_stropt(&job.infilename),
+            job.prefixlength,
+        )?)?
+    } else {
+        symbol_arrays(&io::file_or_stdin2symbol_vector(
+            core::stringopt_stropt(&job.infilename),
+            job.prefixlength,
+            args.symbol_width,
+        )?)?
+    };
+    info!("time: {}", now.elapsed().as_millis());
+
+    let mus = get_mus(sa.as_slice(), isa.as_slice(), lcp.as_slice());
+    Ok(vec![report::ResultRecord::new(
+        "mus",
+        "mus",
+        core::get_filename(&job.infilename),
+        sa.len() - 1, //@ the length of the input, without the sentinel
+    )
+    .with("mus_count", mus.len())
+    .with("mus", format!("{:?}", mus).replace(' ', ""))
+    .timed(&now)])
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(args, job)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word;
+
+    #[allow(clippy::needless_borrow)]
+    fn get_mus_from_text(text: &[u8]) -> Vec<(usize, usize)> {
+        let sa = {
+            let mut sa = vec![0; text.len()];
+            cdivsufsort::sort_in_place(&text, sa.as_mut_slice());
+            sa
+        };
+        let (sa, isa, lcp) = build_arrays(text, sa);
+        get_mus(sa.as_slice(), isa.as_slice(), lcp.as_slice())
+    }
+
+    /// A Fibonacci word has two MUSs, at positions f_{n-2} and f_{n-1}, and of lengths f_{n-3} and f_{n-2}, respectively
+    #[test]
+    fn test_mus_fibonacci() {
+        for i in 4..16 {
+            let mut text = word::fibonacci_word(i);
+            text.push(0);
+            let mus = get_mus_from_text(text.as_slice());
+            assert_eq!(mus.len(), 2);
+            assert_eq!(mus[0].0, word::fibonacci_number(i - 2) - 1);
+            assert_eq!(mus[0].1, word::fibonacci_number(i - 3));
+            assert_eq!(mus[1].0, word::fibonacci_number(i - 1) - 1);
+            assert_eq!(mus[1].1, word::fibonacci_number(i - 2));
+        }
+    }
+
+    /// MUSs only depend on the order of the characters, not on their values
+    #[test]
+    fn test_mus_integer_alphabet() {
+        for text in core::RandomStringGenerator::new(0..1024, 2) {
+            let symbols: Vec<u64> = text.iter().map(|&c| 1000 * c as u64).collect();
+            let (sa, isa, lcp) = build_arrays(
+                &symbols,
+                core::suffixarray_prefix_doubling(&symbols).unwrap(),
+            );
+            assert_eq!(get_mus(&sa, &isa, &lcp), get_mus_from_text(&text));
+        }
+    }
+
+    /// reading the same text byte-wise or as symbols gives the same MUSs
+    #[test]
+    fn test_mus_byte_and_symbol_input() {
+        for text in core::RandomStringGenerator::new(0..256, 2) {
+            let symbols: Vec<u64> = text.iter().map(|&c| c as u64).collect();
+            let (sa, isa, lcp) = byte_arrays(&text).unwrap();
+            let (symbol_sa, symbol_isa, symbol_lcp) = symbol_arrays(&symbols).unwrap();
+            assert_eq!(sa.len(), symbol_sa.len());
+            assert_eq!(
+                get_mus(&sa, &isa, &lcp),
+                get_mus(&symbol_sa, &symbol_isa, &symbol_lcp)
+            );
+        }
+        let (sa, isa, lcp) = byte_arrays(b"").unwrap();
+        assert!(get_mus(&sa, &isa, &lcp).is_empty());
+    }
+}
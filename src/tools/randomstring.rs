@@ -0,0 +1,89 @@
+use super::GlobalArgs;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::random;
+use log::info;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// generates a random string, uniformly over an alphabet, with given letter probabilities, from a
+/// Markov source trained on the input, or by mutating copies of the input
+#[derive(clap::Args, Debug)]
+#[group(skip)]
+#[command(group(clap::ArgGroup::new("source").args(["distribution", "markov", "mutation_rate"])))]
+pub struct Args {
+    /// the number of distinct characters of the uniform string, taken from a-z, A-Z, 0-9 (0 for all 62 alphanumeric characters)
+    #[arg(
+        short = 's',
+        long,
+        visible_alias = "alphabet-size",
+        default_value_t = 0
+    )]
+    sigma: usize,
+
+    /// the letters of the uniform string, instead of the first sigma alphanumeric characters
+    #[arg(long, conflicts_with = "sigma")]
+    letters: Option<String>,
+
+    /// the probabilities of the letters drawn independently, like `a:0.5,b:0.25,c:0.25`
+    #[arg(short, long)]
+    distribution: Option<String>,
+
+    /// the order k of a Markov source trained on the input, drawing each letter according to its preceding k letters
+    #[arg(long)]
+    markov: Option<usize>,
+
+    /// the probability of mutating a letter of a copy of the input, generating a repetitive string of mutated copies
+    #[arg(long)]
+    mutation_rate: Option<f64>,
+
+    /// the seed of the random number generator (default: a random seed, logged with -v)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// the length of the string to generate
+    #[arg(short, long, default_value_t = 10)]
+    length: usize,
+}
+
+/// the options of the former binary, where `-a` is the alphabet size and `-p` is the length
+/// instead of the prefix length of the input
+pub fn legacy_options(command: clap::Command) -> clap::Command {
+    command
+        .mut_arg("prefixlength", |arg| arg.short(None))
+        .mut_arg("sigma", |arg| arg.short('a').visible_short_alias('s'))
+        .mut_arg("length", |arg| arg.short('p').visible_short_alias('l'))
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let seed = args.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    info!("seed: {}", seed);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+
+    //@ only the Markov source and the mutation read an input
+    if args.markov.is_none() && args.mutation_rate.is_none() {
+        global.check_no_prefixlength("randomstring")?;
+    }
+    let text = if let Some(spec) = &args.distribution {
+        random::ZerothOrderSource::parse(spec)?.string(&mut rng, args.length)
+    } else if let Some(order) = args.markov {
+        let training =
+            io::file_or_stdin2byte_vector(global.infilename.as_deref(), global.prefixlength)?;
+        random::MarkovSource::train(&training, order)?.string(&mut rng, args.length)
+    } else if let Some(rate) = args.mutation_rate {
+        let base =
+            io::file_or_stdin2byte_vector(global.infilename.as_deref(), global.prefixlength)?;
+        random::repetitive_string(&mut rng, &base, rate, args.length)?
+    } else {
+        let alphabet = match &args.letters {
+            Some(letters) => letters.as_bytes(),
+            None => random::alphanumeric_alphabet(args.sigma)?,
+        };
+        random::uniform_string(&mut rng, alphabet, args.length)?
+    };
+    writer.write_all(&text)?;
+    Ok(())
+}
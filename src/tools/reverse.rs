@@ -0,0 +1,31 @@
+use super::GlobalArgs;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+
+use log::info;
+
+/// reverts all bytes of a given file
+#[derive(clap::Args, Debug)]
+pub struct Args {}
+
+/// the same can be achieved by the UNIX tools rev and tac, but these only work with valid
+/// encodings, and do not work on binary files in general.
+pub fn run(global: &GlobalArgs, _args: &Args) -> Result<()> {
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+
+    info!("prefix_length: {}", global.prefixlength);
+
+    info!("read text");
+
+    let mut text = io::file_or_stdin2byte_vector(
+        core::stringopt_stropt(&global.infilename),
+        global.prefixlength,
+    )?;
+
+    info!("compute reverse");
+    text.reverse();
+    writer.write_all(text.as_slice())?;
+    writer.flush()?;
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use log::info;
+
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+use crate::rindex::RIndex;
+
+/// builds the r-index of a text, reports its size in words, and optionally searches for patterns
+/// given line by line in a file
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the file containing the patterns, one per line
+    #[arg(short = 'f', long)]
+    patternfilename: Option<String>,
+
+    /// also locate the occurrences, not only count them
+    #[arg(short, long)]
+    locate: bool,
+
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+}
+
+/// builds the r-index of the input of `job` and searches the patterns in it
+fn run_job(
+    global: &GlobalArgs,
+    args: &Args,
+    job: &batch::Job,
+) -> Result<Vec<report::ResultRecord>> {
+    info!("filename: {}", core::get_filename(&job.infilename));
+    info!("prefixlength: {}", job.prefixlength);
+
+    use std::time::Instant;
+    let now = Instant::now();
+
+    info!("read text");
+    let text =
+        io::file_or_stdin2mapped_bytes(core::stringopt_stropt(&job.infilename), job.prefixlength)?;
+
+    info!("build r-index");
+    let index = RIndex::new(&text)?;
+
+    let mut records = vec![report::ResultRecord::new(
+        "rindex",
+        "r_index",
+        core::get_filename(&job.infilename),
+        text.len(),
+    )
+    .with("bwt_runs", index.number_of_runs())
+    .with("rlbwt_words", index.rlbwt().size_in_words())
+    .with("index_words", index.size_in_words())
+    .timed(&now)];
+
+    if let Some(patternfilename) = &args.patternfilename {
+        info!("read patterns");
+        let pattern_text = io::file2mapped_bytes(patternfilename, 0)?;
+        let patterns: Vec<&[u8]> = io::lines(&pattern_text)
+            .filter(|pattern| !pattern.is_empty())
+            .collect();
+
+        info!("search patterns");
+        let now = Instant::now();
+        let mut writer = match &global.outfilename {
+            Some(filename) => Some(io::stream_or_stdout(Some(filename))?),
+            None => None,
+        };
+        let mut occurrences = 0;
+        for pattern in patterns.iter() {
+            if args.locate {
+                let positions = index.locate(pattern);
+                occurrences += positions.len();
+                if let Some(writer) = writer.as_mut() {
+                    writeln!(writer, "{} {:?}", positions.len(), positions)?;
+                }
+            } else {
+                let count = index.count(pattern);
+                occurrences += count;
+                if let Some(writer) = writer.as_mut() {
+                    writeln!(writer, "{}", count)?;
+                }
+            }
+        }
+        records.push(
+            report::ResultRecord::new(
+                "rindex",
+                "r_index_search",
+                core::get_filename(&job.infilename),
+                text.len(),
+            )
+            .with("locate", args.locate)
+            .with("patterns", patterns.len())
+            .with("occurrences", occurrences)
+            .timed(&now),
+        );
+    }
+    Ok(records)
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::check_single_output(&jobs, &global.outfilename)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(global, args, job)
+    })
+}
@@ -0,0 +1,162 @@
+// extern crate byte_string;
+
+use super::GlobalArgs;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use more_asserts::assert_lt;
+
+pub fn rle_text<R: std::io::Read, W: std::io::Write>(
+    reader: &mut R,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let mut bytes = io::ByteReader::new(reader);
+    if let Some(first_char) = bytes.next_byte()? {
+        let mut run_counter = 0_u8; //@ counts the number of character runs
+        let mut prev_char = first_char; //@ the current character of the chracter run
+        for next_char in bytes {
+            let next_char = next_char?;
+            if next_char != prev_char {
+                write!(writer, "({},{})", prev_char as char, run_counter + 1)?;
+                prev_char = next_char;
+                run_counter = 0;
+            } else {
+                run_counter += 1;
+            }
+        }
+        write!(writer, "({},{})", prev_char as char, run_counter + 1)?;
+    }
+    writer.flush()
+}
+
+pub fn rle<R: std::io::Read, W: std::io::Write>(
+    reader: &mut R,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let mut bytes = io::ByteReader::new(reader);
+    if let Some(first_char) = bytes.next_byte()? {
+        let mut run_counter = 0_u8; //@ counts the number of character runs
+        let mut prev_char = first_char; //@ the current character of the chracter run
+        for next_char in bytes {
+            let next_char = next_char?;
+            if next_char != prev_char {
+                assert_lt!(run_counter, u8::MAX);
+                writer.write_all(&[prev_char, run_counter])?;
+                prev_char = next_char;
+                run_counter = 0;
+            } else {
+                run_counter += 1;
+            }
+        }
+        writer.write_all(&[prev_char, run_counter])?;
+    }
+    writer.flush()
+}
+
+const CHR_ZERO: u8 = b'0';
+
+pub fn rle_zero<R: std::io::Read, W: std::io::Write>(
+    reader: &mut R,
+    writer: &mut W,
+) -> std::io::Result<()> {
+    let mut bytes = io::ByteReader::new(reader);
+    if let Some(first_char) = bytes.next_byte()? {
+        let mut run_counter = 0; //@ counts the number of character runs
+        if first_char == CHR_ZERO {
+            run_counter = 1;
+        } else {
+            writer.write_all(&[first_char])?;
+        }
+        for next_char in bytes {
+            let next_char = next_char?;
+            if next_char != CHR_ZERO {
+                if run_counter > 0 {
+                    assert_lt!(run_counter - 1, u8::MAX);
+                    writer.write_all(&[CHR_ZERO, run_counter - 1])?;
+                    run_counter = 0;
+                }
+                writer.write_all(&[next_char])?;
+            } else {
+                run_counter += 1;
+            }
+        }
+        if run_counter > 0 {
+            assert_lt!(run_counter - 1, u8::MAX);
+            writer.write_all(&[CHR_ZERO, run_counter - 1])?;
+        }
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+fn encode<F: FnOnce(&mut &[u8], &mut Vec<u8>) -> std::io::Result<()>>(
+    mut input: &[u8],
+    f: F,
+) -> Vec<u8> {
+    let mut output = Vec::new();
+    f(&mut input, &mut output).unwrap();
+    output
+}
+
+#[test]
+fn test_rle() {
+    assert_eq!(encode(b"", |r, w| rle(r, w)), b"");
+    assert_eq!(encode(b"aaab", |r, w| rle(r, w)), [b'a', 2, b'b', 0]);
+    assert_eq!(encode(b"aaab", |r, w| rle_text(r, w)), b"(a,3)(b,1)");
+    assert_eq!(
+        encode(b"a00b", |r, w| rle_zero(r, w)),
+        [b'a', b'0', 1, b'b']
+    );
+    //@ runs crossing the boundaries of the read buffer
+    let text: Vec<u8> = (0..3 * io::BYTE_READER_BUFFER_SIZE + 5)
+        .map(|i| (i / 7 % 3) as u8)
+        .collect();
+    let encoded = encode(&text, |r, w| rle(r, w));
+    assert_eq!(
+        encoded.len(),
+        2 * core::number_of_runs(&mut text.as_slice()).unwrap()
+    );
+    assert!(encoded.chunks(2).all(|run| run[1] < 7));
+}
+
+/// computes the run-length encoding
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the length of the prefix to parse
+    #[arg(short = 'z', long)]
+    onlyzero: bool,
+
+    /// human-readable output
+    #[arg(short = 'H', long)]
+    human: bool,
+}
+
+/// the options of the former binary, where `-h` is the human-readable output instead of the help
+pub fn legacy_options(command: clap::Command) -> clap::Command {
+    command
+        .disable_help_flag(true)
+        .arg(
+            clap::Arg::new("help")
+                .long("help")
+                .action(clap::ArgAction::Help)
+                .help("Print help"),
+        )
+        .mut_arg("human", |arg| arg.short('h').visible_short_alias('H'))
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+    let mut reader = io::prefix_stream_or_stdin(
+        core::stringopt_stropt(&global.infilename),
+        global.prefixlength,
+    )?;
+
+    if args.human {
+        rle_text(&mut reader, &mut writer)?;
+    } else if args.onlyzero {
+        rle_zero(&mut reader, &mut writer)?;
+    } else {
+        rle(&mut reader, &mut writer)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use log::info;
+
+use super::GlobalArgs;
+use crate::error::{Error, Result};
+use crate::measure::Measure;
+use crate::necklace::NecklaceKind;
+use crate::report;
+use crate::search::{self, Extremes, SearchSpace};
+
+/// searches all strings, necklaces, bracelets or Lyndon words of a given length exhaustively for
+/// those with the smallest and the largest value of one measure, or of the ratio of two measures
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the length of the strings to search
+    #[arg(short, long, default_value_t = 8)]
+    length: usize,
+
+    /// search all lengths from --length up to this length, each reported separately
+    #[arg(long)]
+    max_length: Option<usize>,
+
+    /// the alphabet size of the strings, whose letters are a, b, ...
+    #[arg(short, long, default_value_t = 2)]
+    sigma: usize,
+
+    /// search only the words of this kind instead of all strings
+    #[arg(short, long, value_enum)]
+    kind: Option<NecklaceKind>,
+
+    /// one measure, or two comma-separated measures whose ratio is also reported
+    #[arg(short, long, value_enum, value_delimiter = ',', num_args(1..=2), default_value = "r")]
+    measure: Vec<Measure>,
+
+    /// evaluate only the smallest string among the renamings of its letters (and its rotations for necklaces)
+    #[arg(short, long)]
+    canonical: bool,
+
+    /// the number of threads evaluating the strings (0: the number of cores)
+    #[arg(short = 'j', long, default_value_t = 0)]
+    threads: usize,
+}
+
+/// adds the extremes of `name` with their witnesses to `record`
+fn push_extremes<T: Into<report::Value> + Copy>(
+    record: &mut report::ResultRecord,
+    name: &str,
+    extremes: &Extremes<T>,
+) {
+    record.push(&format!("min_{}", name), extremes.min);
+    record.push(
+        &format!("min_{}_witness", name),
+        String::from_utf8_lossy(&extremes.min_witness).into_owned(),
+    );
+    record.push(&format!("max_{}", name), extremes.max);
+    record.push(
+        &format!("max_{}_witness", name),
+        String::from_utf8_lossy(&extremes.max_witness).into_owned(),
+    );
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    global.check_no_prefixlength("search")?;
+    let max_length = args.max_length.unwrap_or(args.length);
+    if max_length < args.length {
+        return Err(Error::InvalidArgument(format!(
+            "the maximum length {} is smaller than the length {}",
+            max_length, args.length
+        )));
+    }
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.threads)
+        .build()
+        .map_err(|e| Error::InvalidArgument(format!("cannot create the thread pool: {}", e)))?;
+    let algorithm = args
+        .measure
+        .iter()
+        .map(Measure::name)
+        .collect::<Vec<_>>()
+        .join("/");
+    let words = match args.kind {
+        Some(kind) => clap::ValueEnum::to_possible_value(&kind)
+            .unwrap()
+            .get_name()
+            .to_owned(),
+        None => String::from("all"),
+    };
+
+    let mut reporter = report::Reporter::new(global.format);
+    for length in args.length..=max_length {
+        info!("search the strings of length {}", length);
+        let now = std::time::Instant::now();
+        let space = SearchSpace {
+            length,
+            sigma: args.sigma,
+            kind: args.kind,
+            canonical: args.canonical,
+        };
+        let summary = pool.install(|| search::search(&space, &args.measure))?;
+
+        let mut record = report::ResultRecord::new("search", &algorithm, "", length)
+            .with("sigma", args.sigma)
+            .with("words", words.as_str())
+            .with("canonical", args.canonical)
+            .with("strings", summary.strings);
+        for (measure, extremes) in args.measure.iter().zip(&summary.measures) {
+            if let Some(extremes) = extremes {
+                push_extremes(&mut record, &measure.name().replace('-', "_"), extremes);
+            }
+        }
+        if let Some(ratio) = &summary.ratio {
+            push_extremes(&mut record, "ratio", ratio);
+        }
+        reporter.emit(record.timed(&now));
+    }
+    Ok(())
+}
@@ -0,0 +1,179 @@
+use log::info;
+
+use super::GlobalArgs;
+use crate::core;
+use crate::error::{Error, Result};
+use crate::io;
+use crate::measure::Measure;
+use crate::report;
+use crate::search::SearchSpace;
+use crate::sensitivity::{self, Change, EditKind, Sensitivity};
+
+/// applies every single edit (substitution, insertion, deletion, rotation) to the input, or to all
+/// strings of a given length, and reports the largest multiplicative and additive changes of a
+/// measure with the witnessing edits
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the measure whose changes are reported
+    #[arg(short, long, value_enum, default_value = "r")]
+    measure: Measure,
+
+    /// comma-separated kinds of edits, each reported separately
+    #[arg(
+        short,
+        long,
+        value_enum,
+        value_delimiter = ',',
+        default_value = "substitution,insertion,deletion,rotation"
+    )]
+    edits: Vec<EditKind>,
+
+    /// the letters to substitute and insert (otherwise the letters of the input)
+    #[arg(short, long, conflicts_with = "length")]
+    alphabet: Option<String>,
+
+    /// edit all strings of this length instead of the input
+    #[arg(short, long)]
+    length: Option<usize>,
+
+    /// the alphabet size of the strings of the given length, whose letters are a, b, ...
+    #[arg(short, long, default_value_t = 2)]
+    sigma: usize,
+
+    /// edit only the smallest string among the renamings of its letters
+    #[arg(short, long, requires = "length")]
+    canonical: bool,
+
+    /// the number of threads evaluating the edits (0: the number of cores)
+    #[arg(short = 'j', long, default_value_t = 0)]
+    threads: usize,
+}
+
+/// adds the value of `change` under `key` with its edit, and with its string if `witness` is
+/// set, to `record`, or placeholders if there is no change
+fn push_change<V: Into<report::Value>>(
+    record: &mut report::ResultRecord,
+    key: &str,
+    change: &Option<Change>,
+    value: impl Fn(&Change) -> V,
+    placeholder: V,
+    witness: bool,
+) {
+    record.push(key, change.as_ref().map_or(placeholder, &value));
+    record.push(
+        &format!("{}_edit", key),
+        change
+            .as_ref()
+            .map_or(String::from("-"), |c| c.edit.to_string()),
+    );
+    if witness {
+        record.push(
+            &format!("{}_witness", key),
+            change.as_ref().map_or(String::from("-"), |c| {
+                String::from_utf8_lossy(&c.text).into_owned()
+            }),
+        );
+    }
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(args.threads)
+        .build()
+        .map_err(|e| Error::InvalidArgument(format!("cannot create the thread pool: {}", e)))?;
+
+    let text = match args.length {
+        Some(_) => {
+            //@ the exhaustive evaluation generates its strings instead of reading an input
+            global.check_no_prefixlength("sensitivity --length")?;
+            None
+        }
+        None => Some(io::file_or_stdin2byte_vector(
+            core::stringopt_stropt(&global.infilename),
+            global.prefixlength,
+        )?),
+    };
+    let alphabet = match (&args.alphabet, &text) {
+        (Some(alphabet), _) => alphabet.as_bytes().to_vec(),
+        (None, Some(text)) => {
+            let mut letters = text.clone();
+            letters.sort_unstable();
+            letters.dedup();
+            letters
+        }
+        (None, None) => Vec::new(),
+    };
+
+    let mut reporter = report::Reporter::new(global.format);
+    for &kind in &args.edits {
+        info!("evaluate the {} edits", kind.name());
+        let now = std::time::Instant::now();
+        let (input, n, result): (&str, usize, Sensitivity) = match (&text, args.length) {
+            (Some(text), _) => (
+                core::get_filename(&global.infilename),
+                text.len(),
+                pool.install(|| sensitivity::sensitivity(text, &alphabet, kind, args.measure))?,
+            ),
+            (None, Some(length)) => {
+                let space = SearchSpace {
+                    length,
+                    sigma: args.sigma,
+                    kind: None,
+                    canonical: args.canonical,
+                };
+                (
+                    "",
+                    length,
+                    pool.install(|| {
+                        sensitivity::exhaustive_sensitivity(&space, kind, args.measure)
+                    })?,
+                )
+            }
+            (None, None) => unreachable!("the input is read if no length is given"),
+        };
+
+        let mut record = report::ResultRecord::new("sensitivity", args.measure.name(), input, n)
+            .with("edit", kind.name())
+            .with("strings", result.strings)
+            .with("edits", result.edits);
+        let witness = text.is_none();
+        if let Some(text) = &text {
+            record.push("value", args.measure.evaluate(text)?);
+        }
+        let ratio = |c: &Change| c.ratio().unwrap();
+        push_change(
+            &mut record,
+            "max_ratio",
+            &result.max_ratio,
+            ratio,
+            f64::NAN,
+            witness,
+        );
+        push_change(
+            &mut record,
+            "min_ratio",
+            &result.min_ratio,
+            ratio,
+            f64::NAN,
+            witness,
+        );
+        push_change(
+            &mut record,
+            "max_increase",
+            &result.max_increase,
+            Change::increase,
+            0,
+            witness,
+        );
+        push_change(
+            &mut record,
+            "max_decrease",
+            &result.max_decrease,
+            Change::decrease,
+            0,
+            witness,
+        );
+        reporter.emit(record.timed(&now));
+    }
+    Ok(())
+}
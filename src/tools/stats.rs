@@ -0,0 +1,74 @@
+use super::GlobalArgs;
+use super::{count_sigma, count_z, entropy};
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+
+use log::info;
+use std::time::Instant;
+
+/// computes the length, the alphabet size, the number of BWT runs r, the number of LZ77
+/// factors z, and the empirical entropies up to a given order
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// the largest order of the reported entropies
+    #[arg(short = 'k', long, default_value_t = 4)]
+    max_order: usize,
+
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+}
+
+/// computes the statistics of the input of `job`
+fn run_job(args: &Args, job: &batch::Job) -> Result<Vec<report::ResultRecord>> {
+    info!("filename: {}", core::get_filename(&job.infilename));
+    info!("prefixlength: {}", job.prefixlength);
+
+    let now = Instant::now();
+    info!("read text");
+    let mut text =
+        io::file_or_stdin2byte_vector(core::stringopt_stropt(&job.infilename), job.prefixlength)?;
+    let mut record = report::ResultRecord::new(
+        "stats",
+        "stats",
+        core::get_filename(&job.infilename),
+        text.len(),
+    );
+
+    info!("compute sigma");
+    record.push("sigma", count_sigma::count_sigma(text.iter()));
+
+    info!("compute z");
+    let z = if text.is_empty() {
+        0
+    } else {
+        let sa = count_z::byte_suffix_array(&text);
+        count_z::compute_factors(&text, &sa, &mut Instant::now()).len()
+    };
+
+    //@ r and the entropies are computed on the text with the dollar sign, like count_r and entropy
+    text.push(0u8);
+    info!("compute r");
+    let bwt = core::bwt_from_text_by_sa(&text)?;
+    record.push("r", core::number_of_runs(&mut bwt.as_slice())?);
+    record.push("z", z);
+
+    info!("compute entropies");
+    record.push("H_0", entropy::zero_order_entropy(text.iter()));
+    for order in 1..=args.max_order {
+        record.push(
+            &format!("H_{}", order),
+            entropy::kth_order_entropy(&text, order),
+        );
+    }
+    Ok(vec![record.timed(&now)])
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(args, job)
+    })
+}
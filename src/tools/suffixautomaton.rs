@@ -0,0 +1,139 @@
+use log::info;
+
+use super::GlobalArgs;
+use crate::batch;
+use crate::core;
+use crate::error::Result;
+use crate::io;
+use crate::report;
+use crate::suffixautomaton::SuffixAutomaton;
+
+/// builds the suffix automaton (DAWG) online and counts distinct substrings
+#[derive(clap::Args, Debug)]
+pub struct Args {
+    /// patterns whose number of occurrences are reported
+    #[arg(long, num_args(1..))]
+    pattern: Vec<String>,
+
+    /// a second file for computing the longest common substring with the input, read in full
+    #[arg(short, long)]
+    secondfilename: Option<String>,
+
+    /// output the substring complexity d_k for all k up to this length
+    #[arg(short, long, default_value_t = 0)]
+    max_order: usize,
+
+    #[command(flatten)]
+    batch: batch::BatchArgs,
+
+    #[command(flatten)]
+    sweep: batch::SweepArgs,
+}
+
+/// builds the suffix automaton of the input of `job` and queries it
+fn run_job(args: &Args, job: &batch::Job) -> Result<Vec<report::ResultRecord>> {
+    info!("filename: {}", core::get_filename(&job.infilename));
+    info!("prefixlength: {}", job.prefixlength);
+
+    use std::time::Instant;
+    let now = Instant::now();
+
+    info!("build suffix automaton");
+    let reader = io::stream_or_stdin(core::stringopt_stropt(&job.infilename))?;
+    let mut automaton = SuffixAutomaton::new();
+    let mut records = Vec::new();
+    let automaton_record = |automaton: &SuffixAutomaton<u8>| {
+        report::ResultRecord::new(
+            "suffixautomaton",
+            "suffixautomaton",
+            core::get_filename(&job.infilename),
+            automaton.len(),
+        )
+        .with("states", automaton.number_of_states())
+        .with("transitions", automaton.number_of_transitions())
+        .with(
+            "distinct_substrings",
+            automaton.number_of_distinct_substrings(),
+        )
+        .timed(&now)
+    };
+    //@ in a sweep, the prefixes are reported while the automaton grows
+    let mut next_step = args.sweep.next_step(0)?;
+    let mut characters = io::ByteReader::new(reader);
+    loop {
+        if next_step == Some(automaton.len()) {
+            records.push(automaton_record(&automaton));
+            next_step = args.sweep.next_step(automaton.len() + 1)?;
+        }
+        if job.prefixlength > 0 && automaton.len() >= job.prefixlength {
+            break;
+        }
+        match characters.next() {
+            Some(c) => automaton.extend(c?),
+            None => break,
+        }
+    }
+    //@ the steps beyond the text length are cut down to the text length
+    let reported = records.last().map(|record: &report::ResultRecord| record.n);
+    if !args.sweep.is_enabled() || (next_step.is_some() && reported != Some(automaton.len())) {
+        records.push(automaton_record(&automaton));
+    }
+
+    let result = |algorithm: &str| {
+        report::ResultRecord::new(
+            "suffixautomaton",
+            algorithm,
+            core::get_filename(&job.infilename),
+            automaton.len(),
+        )
+    };
+
+    if args.max_order > 0 {
+        let now = Instant::now();
+        let complexity = automaton.substring_complexity(args.max_order);
+        for (k, d) in complexity.iter().enumerate().skip(1) {
+            records.push(
+                result("substring_complexity")
+                    .with("k", k)
+                    .with("d_k", *d)
+                    .timed(&now),
+            );
+        }
+    }
+
+    if !args.pattern.is_empty() {
+        let counts = automaton.occurrence_counts();
+        for pattern in args.pattern.iter() {
+            let now = Instant::now();
+            let occurrences = automaton.count_occurrences(&counts, pattern.as_bytes());
+            records.push(
+                result("count_occurrences")
+                    .with("pattern", pattern.as_str())
+                    .with("occurrences", occurrences)
+                    .timed(&now),
+            );
+        }
+    }
+
+    if let Some(secondfilename) = &args.secondfilename {
+        //@ the prefix lengths only truncate the indexed input
+        let other = io::file2mapped_bytes(secondfilename, 0)?;
+        let now = Instant::now();
+        let (lcs_length, lcs_position) = automaton.longest_common_substring(&other);
+        records.push(
+            result("longest_common_substring")
+                .with("lcs_length", lcs_length)
+                .with("lcs_position", lcs_position)
+                .with("secondfile", secondfilename.as_str())
+                .timed(&now),
+        );
+    }
+    Ok(records)
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    let jobs = batch::jobs(&global.infilename, global.prefixlength, &args.batch)?;
+    batch::run_and_report(&jobs, args.batch.threads, global.format, |job| {
+        run_job(args, job)
+    })
+}
@@ -0,0 +1,346 @@
+use super::GlobalArgs;
+use crate::core;
+use crate::error::{Error, Result};
+use crate::io;
+use crate::word;
+use log::info;
+
+/// computes a recurrent word, a word generated by a given morphism, or a Sturmian word
+#[derive(clap::Args, Debug)]
+#[group(skip)]
+#[command(group(clap::ArgGroup::new("generator").required(true).args(["name", "morphism", "morphism_file", "directive", "slope", "christoffel", "episturmian", "arnoux_rauzy", "kbonacci", "automatic", "debruijn"])))]
+pub struct Args {
+    /// the index k of the word to compute, or the number of times the morphism is applied
+    #[arg(short)]
+    k: Option<usize>,
+
+    #[arg(short, long, value_enum)]
+    name: Option<WordName>,
+
+    /// a morphism like `a->ab,b->a` given by the images of its letters
+    #[arg(short, long)]
+    morphism: Option<String>,
+
+    /// a file with the rules of the morphism, one per line, where `#` starts a comment
+    #[arg(long)]
+    morphism_file: Option<String>,
+
+    /// the word the morphism is applied to (default: the letter of the first rule)
+    #[arg(long)]
+    start: Option<String>,
+
+    /// the length of the prefix to compute, streamed to the output, instead of the k-th word or applying the morphism k times, or the length of the mechanical word; the finite words of --directive, --christoffel and --debruijn have no such prefixes
+    #[arg(short, long, conflicts_with_all = ["k", "directive", "christoffel", "debruijn"])]
+    length: Option<usize>,
+
+    /// comma-separated directive sequence d_0,d_1,... of the standard Sturmian word s_{k+1} = s_k^{d_k} s_{k-1}
+    #[arg(long, value_delimiter = ',')]
+    directive: Vec<usize>,
+
+    /// the slope in [0, 1] of the mechanical word of the given length
+    #[arg(long, requires = "length")]
+    slope: Option<f64>,
+
+    /// the intercept of the mechanical word (default: the slope, giving the characteristic word)
+    #[arg(long, requires = "slope")]
+    intercept: Option<f64>,
+
+    /// the slope p/q of the Christoffel word, with q letters a and p letters b
+    #[arg(long, value_parser = parse_fraction)]
+    christoffel: Option<(usize, usize)>,
+
+    /// compute the upper instead of the lower mechanical or Christoffel word
+    #[arg(long)]
+    upper: bool,
+
+    /// the directive sequence of letters of the palindromic prefix of a standard episturmian word, repeated up to length k if given
+    #[arg(long)]
+    episturmian: Option<String>,
+
+    /// like --episturmian, but checks that the directive sequence contains all letters from a to its largest letter
+    #[arg(long)]
+    arnoux_rauzy: Option<String>,
+
+    /// the order of the k-th k-bonacci word, i.e., the fixed point of a -> ab, b -> ac, ..., with 2 for Fibonacci and 3 for Tribonacci
+    #[arg(long)]
+    kbonacci: Option<usize>,
+
+    /// the automatic sequence of the given length generated by a DFAO
+    #[arg(long, value_enum, requires = "length")]
+    automatic: Option<AutomaticName>,
+
+    /// the de Bruijn sequence of the given order, containing each word of this length over --sigma letters exactly once as a cyclic substring
+    #[arg(long)]
+    debruijn: Option<usize>,
+
+    /// extend the de Bruijn sequence such that each word occurs exactly once as a (non-cyclic) substring
+    #[arg(long, requires = "debruijn")]
+    linear: bool,
+
+    /// the number of letters of the generalized Thue-Morse sequence or the de Bruijn sequence
+    #[arg(short, long, default_value_t = 2)]
+    sigma: usize,
+
+    /// a letter-to-letter morphism like `a->0,b->1` applied to the computed word
+    #[arg(short, long)]
+    coding: Option<String>,
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum WordName {
+    Fibonacci,
+    FibonacciLyndonFactor,
+    Tribonacci,
+    Kolakoski,
+    ThueMorse,
+    PeriodDoubling,
+    PaperFolding,
+    QuaternaryPaperFolding,
+    BinaryDeBrujin,
+    Power2,
+    Vtm,
+}
+
+/// the directive sequence given by `letters`, repeated up to length k if given
+fn directive_sequence(letters: &str, k: Option<usize>) -> Vec<u8> {
+    match k {
+        Some(k) => letters.bytes().cycle().take(k).collect(),
+        None => letters.as_bytes().to_vec(),
+    }
+}
+
+/// parses a fraction p/q
+fn parse_fraction(fraction: &str) -> std::result::Result<(usize, usize), String> {
+    let (p, q) = fraction
+        .split_once('/')
+        .ok_or_else(|| format!("'{}' is not a fraction p/q", fraction))?;
+    let parse = |number: &str| {
+        number
+            .trim()
+            .parse::<usize>()
+            .map_err(|e| format!("'{}' is not a fraction p/q: {}", fraction, e))
+    };
+    Ok((parse(p)?, parse(q)?))
+}
+
+#[derive(clap::ValueEnum, Clone, Debug)]
+enum AutomaticName {
+    ThueMorse,
+    RudinShapiro,
+    BaumSweet,
+    Cantor,
+    StewartChoral,
+    MephistoWaltz,
+}
+
+/// the DFAO of the automatic sequence given by the name
+fn automatic_sequence(name: &AutomaticName, sigma: usize) -> Result<word::Dfao> {
+    Ok(match name {
+        AutomaticName::ThueMorse => word::Dfao::thue_morse(sigma)?,
+        AutomaticName::RudinShapiro => word::Dfao::rudin_shapiro(),
+        AutomaticName::BaumSweet => word::Dfao::baum_sweet(),
+        AutomaticName::Cantor => word::Dfao::cantor(),
+        AutomaticName::StewartChoral => word::Dfao::stewart_choral(),
+        AutomaticName::MephistoWaltz => word::Dfao::mephisto_waltz(),
+    })
+}
+
+/// computes the word given by the name and the index k
+fn named_word(name: &WordName, k: usize) -> Vec<u8> {
+    let fun = match name {
+        WordName::Fibonacci => word::fibonacci_word,
+        WordName::FibonacciLyndonFactor => word::fibonacci_lyndonfactor,
+        WordName::Tribonacci => word::tribonacci_word,
+        WordName::Kolakoski => word::kolakoski_word,
+        WordName::ThueMorse => word::thuemorse_word,
+        WordName::PeriodDoubling => word::period_doubling_word,
+        WordName::PaperFolding => word::paperfolding_word,
+        WordName::QuaternaryPaperFolding => word::quaternary_paperfolding_word,
+        WordName::BinaryDeBrujin => word::binary_debruijn_word,
+        WordName::Power2 => word::power2_sequence,
+        WordName::Vtm => word::vtm_word,
+    };
+    fun(k)
+}
+
+/// streams the word given by the name, of which [`named_word`] computes prefixes
+fn named_letters(name: &WordName) -> Result<Box<dyn Iterator<Item = u8>>> {
+    let fixed_point = |letters: &[u8], morphism| {
+        Box::new(
+            word::Morphism::from_fn(letters, morphism)
+                .fixed_point_letters(b'a')
+                .unwrap(),
+        )
+    };
+    Ok(match name {
+        WordName::Fibonacci => fixed_point(b"ab", word::fibonacci_morphism),
+        WordName::Tribonacci => fixed_point(b"abc", word::tribonacci_morphism),
+        WordName::ThueMorse => fixed_point(b"ab", word::thuemorse_morphism),
+        WordName::PeriodDoubling => fixed_point(b"ab", word::period_doubling_morphism),
+        WordName::Vtm => fixed_point(b"abc", word::vtm_morphism),
+        WordName::Kolakoski => Box::new(word::KolakoskiLetters::new()),
+        WordName::PaperFolding => Box::new(word::paperfolding_letters()),
+        WordName::QuaternaryPaperFolding => Box::new(word::quaternary_paperfolding_letters()),
+        WordName::Power2 => Box::new(word::power2_letters()),
+        WordName::FibonacciLyndonFactor | WordName::BinaryDeBrujin => {
+            return Err(Error::InvalidArgument(format!(
+                "the word {} is not the prefix of an infinite word, use the index -k instead of the length -l",
+                clap::ValueEnum::to_possible_value(name).unwrap().get_name()
+            )))
+        }
+    })
+}
+
+/// computes the word generated by `morphism` as specified by `args`, streaming the fixed point if
+/// its length is given
+fn morphic_word(morphism: &word::Morphism, args: &Args) -> Result<Box<dyn Iterator<Item = u8>>> {
+    let non_prolongable = morphism.non_prolongable_letters();
+    if !non_prolongable.is_empty() {
+        info!(
+            "non-prolongable letters: {}",
+            String::from_utf8_lossy(&non_prolongable)
+        );
+    }
+    let start = match &args.start {
+        Some(start) => start.as_bytes().to_vec(),
+        None => vec![morphism.letters()[0]],
+    };
+    match (args.length, args.k) {
+        (Some(length), _) => match start.as_slice() {
+            [letter] => {
+                let letters = morphism.fixed_point_letters(*letter)?;
+                if letters.is_finite() {
+                    //@ reports a fixed point shorter than `length`
+                    Ok(Box::new(morphism.fixed_point(*letter, length)?.into_iter()))
+                } else {
+                    Ok(Box::new(letters))
+                }
+            }
+            _ => Err(Error::InvalidArgument(String::from(
+                "a fixed point starts with a single letter",
+            ))),
+        },
+        (None, Some(k)) => Ok(Box::new(morphism.iterate(&start, k)?.into_iter())),
+        (None, None) => Err(Error::InvalidArgument(String::from(
+            "either the number of iterations -k or the length -l of the fixed point is required",
+        ))),
+    }
+}
+
+/// writes the first `length` letters, or all letters if no length is given, in chunks mapped by the
+/// optional coding
+fn write_letters<W: std::io::Write>(
+    writer: &mut W,
+    mut letters: impl Iterator<Item = u8>,
+    length: Option<usize>,
+    coding: Option<&word::Morphism>,
+) -> Result<()> {
+    const CHUNK_SIZE: usize = 1 << 16;
+    let mut remaining = length.unwrap_or(usize::MAX);
+    let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+    while remaining > 0 {
+        chunk.clear();
+        chunk.extend(letters.by_ref().take(remaining.min(CHUNK_SIZE)));
+        if chunk.is_empty() {
+            break;
+        }
+        remaining -= chunk.len();
+        match coding {
+            Some(coding) => writer.write_all(&coding.apply(&chunk)?)?,
+            None => writer.write_all(&chunk)?,
+        }
+    }
+    Ok(())
+}
+
+pub fn run(global: &GlobalArgs, args: &Args) -> Result<()> {
+    global.check_no_prefixlength("word")?;
+    let mut writer = io::stream_or_stdout(core::stringopt_stropt(&global.outfilename))?;
+
+    let coding = match &args.coding {
+        Some(coding) => {
+            let coding = word::Morphism::parse(coding)?;
+            if !coding.is_coding() {
+                return Err(Error::InvalidArgument(String::from(
+                    "the coding must map each letter to a single letter",
+                )));
+            }
+            Some(coding)
+        }
+        None => None,
+    };
+    let morphism = match (&args.morphism, &args.morphism_file) {
+        (Some(spec), _) => Some(word::Morphism::parse(spec)?),
+        (None, Some(filename)) => Some(word::Morphism::from_file(filename)?),
+        (None, None) => None,
+    };
+    let text = if let Some(morphism) = &morphism {
+        let letters = morphic_word(morphism, args)?;
+        return write_letters(&mut writer, letters, args.length, coding.as_ref());
+    } else if !args.directive.is_empty() {
+        word::standard_word(&args.directive)?
+    } else if let Some(slope) = args.slope {
+        let length = args.length.unwrap_or_default();
+        let intercept = args.intercept.unwrap_or(slope);
+        word::mechanical_word(slope, intercept, length, args.upper)?
+    } else if let Some((p, q)) = args.christoffel {
+        word::christoffel_word(p, q, args.upper)?
+    } else if let Some(letters) = args.episturmian.as_ref().or(args.arnoux_rauzy.as_ref()) {
+        //@ with a length, the directive sequence repeats periodically, giving an infinite word
+        let directive = match args.length {
+            Some(_) => letters.as_bytes().to_vec(),
+            None => directive_sequence(letters, args.k),
+        };
+        if args.arnoux_rauzy.is_some() {
+            let sigma = directive
+                .iter()
+                .max()
+                .map_or(0, |&c| c.saturating_sub(b'a') as usize + 1);
+            word::check_arnoux_rauzy_directive(&directive, sigma)?;
+        }
+        match args.length {
+            Some(length) if directive.is_empty() && length > 0 => {
+                return Err(Error::InvalidArgument(String::from(
+                    "the directive sequence of a prefix of positive length must not be empty",
+                )))
+            }
+            Some(length) => word::episturmian_prefix(directive.iter().copied().cycle(), length),
+            None => word::episturmian_word(&directive),
+        }
+    } else if let Some(order) = args.kbonacci {
+        let morphism = word::kbonacci_morphism(order)?;
+        if let Some(length) = args.length {
+            let letters = morphism.fixed_point_letters(b'a')?;
+            return write_letters(&mut writer, letters, Some(length), coding.as_ref());
+        }
+        let k = args.k.ok_or_else(|| {
+            Error::InvalidArgument(String::from(
+                "either the index -k or the length -l of the word is required",
+            ))
+        })?;
+        word::kbonacci_word(order, k)?
+    } else if let Some(order) = args.debruijn {
+        if args.linear {
+            word::linear_debruijn_word(args.sigma, order)?
+        } else {
+            word::debruijn_word(args.sigma, order)?
+        }
+    } else if let Some(name) = &args.automatic {
+        let dfao = automatic_sequence(name, args.sigma)?;
+        let letters = (0..).map(|n| dfao.letter(n));
+        return write_letters(&mut writer, letters, args.length, coding.as_ref());
+    } else {
+        let name = args.name.as_ref().expect("clap requires a generator");
+        if let Some(length) = args.length {
+            let letters = named_letters(name)?;
+            return write_letters(&mut writer, letters, Some(length), coding.as_ref());
+        }
+        let k = args.k.ok_or_else(|| {
+            Error::InvalidArgument(String::from(
+                "either the index -k or the length -l of the word is required",
+            ))
+        })?;
+        named_word(name, k)
+    };
+    write_letters(&mut writer, text.into_iter(), None, coding.as_ref())
+}
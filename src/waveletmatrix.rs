@@ -0,0 +1,284 @@
+use crate::fmindex::ByteRank;
+use succinct::rank::BitRankSupport;
+use succinct::select::{Select0Support, Select1Support};
+use succinct::{BinSearchSelect, BitVec, BitVecMut, BitVector, Rank9, SpaceUsage};
+
+type Level = BinSearchSelect<Rank9<BitVector<u64>>>;
+
+/// The wavelet matrix of a sequence of integers, storing for each bit of the integers, from the
+/// most significant to the least significant one, a bit vector with rank and select support.
+/// Reference: Francisco Claude, Gonzalo Navarro, Alberto Ordóñez Pereira: "The wavelet matrix:
+/// An efficient wavelet tree for large alphabets". Inf. Syst. 47: 39-52 (2015)
+pub struct WaveletMatrix {
+    levels: Vec<Level>,
+    /// zeros[l] is the number of zero bits at level l
+    zeros: Vec<usize>,
+    length: usize,
+}
+
+impl WaveletMatrix {
+    /// builds the wavelet matrix of `sequence` using the smallest number of bits per value that
+    /// can represent the maximum value
+    pub fn new<T: Copy + Into<u64>>(sequence: &[T]) -> WaveletMatrix {
+        let max_value = sequence.iter().map(|&x| x.into()).max().unwrap_or(0);
+        let bit_width = std::cmp::max(1, 64 - max_value.leading_zeros() as usize);
+        WaveletMatrix::with_bit_width(sequence, bit_width)
+    }
+
+    /// builds the wavelet matrix of `sequence`, whose values have to fit into `bit_width` bits
+    pub fn with_bit_width<T: Copy + Into<u64>>(sequence: &[T], bit_width: usize) -> WaveletMatrix {
+        assert!(bit_width > 0 && bit_width <= 64);
+        let mut current: Vec<u64> = sequence.iter().map(|&x| x.into()).collect();
+        if bit_width < 64 {
+            assert!(current.iter().all(|&x| x >> bit_width == 0));
+        }
+        let mut levels = Vec::with_capacity(bit_width);
+        let mut zeros = Vec::with_capacity(bit_width);
+        for level in 0..bit_width {
+            let shift = bit_width - 1 - level;
+            let mut bits = BitVector::with_fill(current.len() as u64, false);
+            let mut zero_values = Vec::with_capacity(current.len());
+            let mut one_values = Vec::new();
+            for (i, &x) in current.iter().enumerate() {
+                if (x >> shift) & 1 == 1 {
+                    bits.set_bit(i as u64, true);
+                    one_values.push(x);
+                } else {
+                    zero_values.push(x);
+                }
+            }
+            zeros.push(zero_values.len());
+            levels.push(BinSearchSelect::new(Rank9::new(bits)));
+            //@ stably partition by the current bit to obtain the sequence of the next level
+            zero_values.extend_from_slice(&one_values);
+            current = zero_values;
+        }
+        WaveletMatrix {
+            levels,
+            zeros,
+            length: sequence.len(),
+        }
+    }
+
+    /// number of bits per value
+    pub fn bit_width(&self) -> usize {
+        self.levels.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// number of ones in level[0..i)
+    fn rank1(level: &Level, i: usize) -> usize {
+        if i == 0 {
+            0
+        } else {
+            level.rank1(i as u64 - 1) as usize
+        }
+    }
+
+    /// number of zeros in level[0..i)
+    fn rank0(level: &Level, i: usize) -> usize {
+        i - Self::rank1(level, i)
+    }
+
+    /// the bit of `value` at level `level`
+    fn bit(&self, value: u64, level: usize) -> bool {
+        (value >> (self.bit_width() - 1 - level)) & 1 == 1
+    }
+
+    /// the value at position `i`
+    pub fn access(&self, i: usize) -> u64 {
+        let mut position = i;
+        let mut value = 0;
+        for (level, bits) in self.levels.iter().enumerate() {
+            value <<= 1;
+            if bits.get_bit(position as u64) {
+                value |= 1;
+                position = self.zeros[level] + Self::rank1(bits, position);
+            } else {
+                position = Self::rank0(bits, position);
+            }
+        }
+        value
+    }
+
+    /// maps the interval [begin, end) of a level to the interval of the next level containing
+    /// the elements whose bit at `level` is `bit`
+    fn descend(&self, level: usize, bit: bool, begin: usize, end: usize) -> (usize, usize) {
+        let bits = &self.levels[level];
+        if bit {
+            (
+                self.zeros[level] + Self::rank1(bits, begin),
+                self.zeros[level] + Self::rank1(bits, end),
+            )
+        } else {
+            (Self::rank0(bits, begin), Self::rank0(bits, end))
+        }
+    }
+
+    /// number of occurrences of `value` in sequence[0..i)
+    pub fn rank(&self, value: u64, i: usize) -> usize {
+        if self.bit_width() < 64 && value >> self.bit_width() != 0 {
+            return 0;
+        }
+        let (mut begin, mut end) = (0, i);
+        for level in 0..self.bit_width() {
+            (begin, end) = self.descend(level, self.bit(value, level), begin, end);
+        }
+        end - begin
+    }
+
+    /// the position of the (k+1)-th occurrence of `value`, if it exists
+    pub fn select(&self, value: u64, k: usize) -> Option<usize> {
+        if self.bit_width() < 64 && value >> self.bit_width() != 0 {
+            return None;
+        }
+        //@ find the interval of `value` in the last level
+        let (mut begin, mut end) = (0, self.length);
+        for level in 0..self.bit_width() {
+            (begin, end) = self.descend(level, self.bit(value, level), begin, end);
+        }
+        if begin + k >= end {
+            return None;
+        }
+        //@ walk up the levels
+        let mut position = begin + k;
+        for level in (0..self.bit_width()).rev() {
+            let bits = &self.levels[level];
+            position = if self.bit(value, level) {
+                bits.select1((position - self.zeros[level]) as u64)? as usize
+            } else {
+                bits.select0(position as u64)? as usize
+            };
+        }
+        Some(position)
+    }
+
+    /// the (k+1)-th smallest value in sequence[begin..end)
+    pub fn range_quantile(&self, begin: usize, end: usize, k: usize) -> u64 {
+        assert!(k < end - begin);
+        let (mut begin, mut end, mut k) = (begin, end, k);
+        let mut value = 0;
+        for level in 0..self.bit_width() {
+            let bits = &self.levels[level];
+            let zeros = Self::rank0(bits, end) - Self::rank0(bits, begin);
+            value <<= 1;
+            if k < zeros {
+                (begin, end) = self.descend(level, false, begin, end);
+            } else {
+                k -= zeros;
+                value |= 1;
+                (begin, end) = self.descend(level, true, begin, end);
+            }
+        }
+        value
+    }
+
+    /// number of distinct values in sequence[begin..end), in O(d lg σ) time for d being the output
+    pub fn range_distinct_count(&self, begin: usize, end: usize) -> usize {
+        self.range_distinct_count_at(0, begin, end)
+    }
+
+    fn range_distinct_count_at(&self, level: usize, begin: usize, end: usize) -> usize {
+        if begin >= end {
+            return 0;
+        }
+        if level == self.bit_width() {
+            return 1;
+        }
+        let (zero_begin, zero_end) = self.descend(level, false, begin, end);
+        let (one_begin, one_end) = self.descend(level, true, begin, end);
+        self.range_distinct_count_at(level + 1, zero_begin, zero_end)
+            + self.range_distinct_count_at(level + 1, one_begin, one_end)
+    }
+
+    /// space of the data structure in bytes
+    pub fn size_in_bytes(&self) -> usize {
+        self.levels.iter().map(|l| l.total_bytes()).sum::<usize>()
+            + self.zeros.len() * std::mem::size_of::<usize>()
+    }
+}
+
+impl ByteRank for WaveletMatrix {
+    fn from_bwt(bwt: &[u8]) -> Self {
+        WaveletMatrix::with_bit_width(bwt, 8)
+    }
+
+    fn rank(&self, c: u8, i: usize) -> usize {
+        WaveletMatrix::rank(self, c as u64, i)
+    }
+
+    fn access(&self, i: usize) -> u8 {
+        WaveletMatrix::access(self, i) as u8
+    }
+
+    fn len(&self) -> usize {
+        self.length
+    }
+
+    fn size_in_bytes(&self) -> usize {
+        WaveletMatrix::size_in_bytes(self)
+    }
+}
+
+#[test]
+fn test_wavelet_matrix() {
+    use rand::{Rng, SeedableRng};
+    let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+    for n in [1, 2, 7, 64, 130] {
+        for sigma in [1_u32, 2, 5, 300] {
+            let sequence: Vec<u32> = (0..n).map(|_| rng.gen_range(0..sigma)).collect();
+            let matrix = WaveletMatrix::new(&sequence);
+            assert_eq!(matrix.len(), n);
+            for (i, &x) in sequence.iter().enumerate() {
+                assert_eq!(matrix.access(i), x as u64);
+            }
+            for value in 0..std::cmp::min(sigma + 1, 8) as u64 {
+                let mut count = 0;
+                for (i, &x) in sequence.iter().enumerate() {
+                    assert_eq!(matrix.rank(value, i), count);
+                    if x as u64 == value {
+                        assert_eq!(matrix.select(value, count), Some(i));
+                        count += 1;
+                    }
+                }
+                assert_eq!(matrix.rank(value, n), count);
+                assert_eq!(matrix.select(value, count), None);
+            }
+            for begin in (0..n).step_by(5) {
+                for end in (begin + 1..=n).step_by(3) {
+                    let mut sorted = sequence[begin..end].to_vec();
+                    sorted.sort_unstable();
+                    for (k, &x) in sorted.iter().enumerate() {
+                        assert_eq!(matrix.range_quantile(begin, end, k), x as u64);
+                    }
+                    sorted.dedup();
+                    assert_eq!(matrix.range_distinct_count(begin, end), sorted.len());
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fmindex_wavelet_matrix() {
+    use crate::fmindex::FMIndex;
+    let text = crate::word::tribonacci_word(10);
+    let index = FMIndex::<WaveletMatrix>::new(&text, 4).unwrap();
+    assert_eq!(index.extract(0, text.len()).unwrap(), text);
+    for pattern in [&b"aba"[..], b"abac", b"cc", b"c"] {
+        let mut naive: Vec<usize> = (0..text.len() - pattern.len() + 1)
+            .filter(|&k| &text[k..k + pattern.len()] == pattern)
+            .collect();
+        let mut located = index.locate(pattern);
+        located.sort_unstable();
+        naive.sort_unstable();
+        assert_eq!(located, naive);
+    }
+}
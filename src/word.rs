@@ -1,6 +1,8 @@
 extern crate env_logger;
 extern crate log;
+use crate::error::{Error, Result};
 use log::info;
+use std::convert::TryFrom;
 
 const CHR_A: u8 = b'a';
 const CHR_B: u8 = b'b';
@@ -10,7 +12,7 @@ const SQRT_5: f64 = 2.23606797749978969641; //(5.0 as f64).sqrt();
 const GOLDEN_RATIO: f64 = (1.0 + SQRT_5) / 2.0;
 const PSI: f64 = -1.0 / GOLDEN_RATIO;
 
-pub fn fibonacci_number(k: u8) -> usize {
+pub fn fibonacci_number(k: usize) -> usize {
     if k == 0 {
         return 1;
     }
@@ -25,14 +27,14 @@ pub fn fibonacci_number(k: u8) -> usize {
 }
 
 /// Estimates the k-th Fibonacci number with Binet's formula
-fn fibonacci_number_estimate(k: u8) -> f64 {
+fn fibonacci_number_estimate(k: usize) -> f64 {
     (GOLDEN_RATIO.powi(k as i32) - PSI.powi(k as i32)) / SQRT_5
 }
 
 /// Computes the k-th Fibonacci word
 /// https://oeis.org/A003849
 /// Reference: Patrice Séébold: "Sequences generated by infinitely iterated morphisms". Discret. Appl. Math. 11(3): 255-264 (1985)
-pub fn fibonacci_word(k: u8) -> Vec<u8> {
+pub fn fibonacci_word(k: usize) -> Vec<u8> {
     let length = (fibonacci_number_estimate(k + 2) + 1.0) as usize + 1;
     let mut text = vec![0_u8; length];
     // let mut text : Vec<u8> = Vec::with_capacity(length);
@@ -102,7 +104,7 @@ fn test_fibonacci_number() {
 /// It uses the fact that
 /// a) TM_k = TM_k-1 \bar{TM_k-1}
 /// b) TM_k is a prefix of TM_{k+1}
-pub fn thuemorse_word(k: u8) -> Vec<u8> {
+pub fn thuemorse_word(k: usize) -> Vec<u8> {
     let n = 1 << k;
     let mut text = vec![0_u8; n];
     // let mut text : Vec<u8> = Vec::with_capacity(n);
@@ -127,13 +129,13 @@ fn test_thuemorse_word() {
 }
 
 /// upper bound on the length of the k-th kolakoski word
-fn kolakoski_length_estimate(k: u8) -> f64 {
+fn kolakoski_length_estimate(k: usize) -> f64 {
     (2.0f64).powi(k as i32)
 }
 
 /// Computes the k-th Kolakoski word starting with 22
 /// https://oeis.org/A078880
-pub fn kolakoski_word(k: u8) -> Vec<u8> {
+pub fn kolakoski_word(k: usize) -> Vec<u8> {
     let length = (kolakoski_length_estimate(k + 2) + 1.0) as usize + 1;
     let mut text = vec![0_u8; length];
     // let mut text : Vec<u8> = Vec::with_capacity(length);
@@ -174,9 +176,7 @@ pub fn kolakoski_word(k: u8) -> Vec<u8> {
             }
         }
     }
-    unsafe {
-        text.set_len(target);
-    }
+    text.truncate(target);
     text
 }
 
@@ -211,7 +211,7 @@ static STR_BA: &[u8] = &[CHR_B, CHR_A];
 
 /// morphism for the Period-doubling sequence
 /// https://oeis.org/A096268
-fn period_doubling_morphism(c: u8) -> &'static [u8] {
+pub fn period_doubling_morphism(c: u8) -> &'static [u8] {
     match c {
         CHR_A => STR_AB,
         _ => STR_AA,
@@ -235,7 +235,7 @@ pub fn fibonacci_morphism(c: u8) -> &'static [u8] {
 /// only works when the morphism is a 2-morphism, meaning that it always maps a character to a
 /// string of length two
 /// we assume that morphism(a) has a as a prefix.
-fn iterate_2morphism(rounds: u8, morphism: fn(u8) -> &'static [u8]) -> Vec<u8> {
+fn iterate_2morphism(rounds: usize, morphism: fn(u8) -> &'static [u8]) -> Vec<u8> {
     if rounds == 0 {
         return vec![CHR_A];
     }
@@ -263,7 +263,7 @@ fn iterate_2morphism(rounds: u8, morphism: fn(u8) -> &'static [u8]) -> Vec<u8> {
 // /// for a general morphism where the k-th word is not a prefix of the (k+1)-st word, we need a
 // /// slower algorithm that iteratively allocates a larger text
 // fn iterate_prefix_morphism_with_startsymbol(
-//     rounds: u8,
+//     rounds: usize,
 //     morphism: fn(u8) -> &'static [u8],
 //     sizehint: usize,
 // 	startsymbol: &[u8]
@@ -296,9 +296,9 @@ fn iterate_2morphism(rounds: u8, morphism: fn(u8) -> &'static [u8]) -> Vec<u8> {
 
 /// for a general morphism where the k-th word is not a prefix of the (k-1)-st word, we need a
 /// slower algorithm that iteratively allocates a larger text
-fn iterate_general_morphism_with_startsymbol(
-    rounds: u8,
-    morphism: fn(u8) -> &'static [u8],
+fn iterate_general_morphism_with_startsymbol<'a, F: Fn(u8) -> &'a [u8]>(
+    rounds: usize,
+    morphism: F,
     sizehint: usize,
     startsymbol: &[u8],
 ) -> Vec<u8> {
@@ -319,9 +319,9 @@ fn iterate_general_morphism_with_startsymbol(
 /// we assume that morphism(a) has a as a prefix, and that the k-th word is a prefix of the
 /// (k+1)-st word
 /// if sizehint > 0, we assume that the sequence to compute fits into this size
-fn iterate_general_morphism(
-    rounds: u8,
-    morphism: fn(u8) -> &'static [u8],
+fn iterate_general_morphism<'a, F: Fn(u8) -> &'a [u8]>(
+    rounds: usize,
+    morphism: F,
     sizehint: usize,
 ) -> Vec<u8> {
     if rounds == 0 {
@@ -348,7 +348,7 @@ fn iterate_general_morphism(
     text
 }
 
-pub fn period_doubling_word(k: u8) -> Vec<u8> {
+pub fn period_doubling_word(k: usize) -> Vec<u8> {
     iterate_2morphism(k, period_doubling_morphism)
 }
 
@@ -406,14 +406,14 @@ const CHR_D: u8 = b'd';
 const CHR_ZERO: u8 = b'0';
 const CHR_ONE: u8 = b'1';
 
-pub fn paperfolding_word(i: u8) -> Vec<u8> {
+pub fn paperfolding_word(i: usize) -> Vec<u8> {
     quaternary_to_binary(&quaternary_paperfolding_word(i))
 }
 
 /// algorithm computing the i-th paperfolding sequence
 /// https://oeis.org/A014577
 /// where a = 00, b = 01, c = 10, d = 11
-pub fn quaternary_paperfolding_word(i: u8) -> Vec<u8> {
+pub fn quaternary_paperfolding_word(i: usize) -> Vec<u8> {
     let n = 1 << i;
     let mut text = vec![0_u8; n];
     // let mut text : Vec<u8> = Vec::with_capacity(n);
@@ -458,6 +458,28 @@ fn test_paperfolding() {
     // [0..100] );
 }
 
+/// morphism whose fixed point starting with d is the quaternary paperfolding word
+pub fn quaternary_paperfolding_morphism(c: u8) -> &'static [u8] {
+    match c {
+        CHR_D => b"db",
+        CHR_B => b"cb",
+        CHR_C => b"da",
+        _ => b"ca",
+    }
+}
+
+/// streams the quaternary paperfolding word, of which [`quaternary_paperfolding_word`] computes prefixes
+pub fn quaternary_paperfolding_letters() -> FixedPointLetters {
+    Morphism::from_fn(b"abcd", quaternary_paperfolding_morphism)
+        .fixed_point_letters(CHR_D)
+        .unwrap()
+}
+
+/// streams the paperfolding word, of which [`paperfolding_word`] computes prefixes
+pub fn paperfolding_letters() -> impl Iterator<Item = u8> {
+    quaternary_paperfolding_letters().flat_map(|c| quaternary_to_binary(&[c]))
+}
+
 fn quaternary_to_binary(text: &[u8]) -> Vec<u8> {
     let n = text.len() << 1;
     let mut output = vec![0_u8; n];
@@ -508,7 +530,8 @@ where
     if j != n {
         return false;
     }
-    true
+    //@ the text is a prefix of a power of its Lyndon prefix of length n-i, and thus a necklace only if it is such a power
+    n.is_multiple_of(n - i)
 }
 
 #[allow(dead_code)]
@@ -522,10 +545,13 @@ fn test_is_necklace() {
     assert!(is_necklace_slice(b"aa"));
     assert!(is_necklace_slice(b"ab"));
     assert!(is_necklace_slice(b"aaa"));
-    assert!(is_necklace_slice(b"aba"));
-    assert!(is_necklace_slice(b"aaba"));
+    assert!(is_necklace_slice(b"aabab"));
     assert!(is_necklace_slice(b"abab"));
-    assert!(is_necklace_slice(b"ababa"));
+    assert!(is_necklace_slice(b"aabaab"));
+    //@ prefixes of necklaces that are not necklaces themselves
+    assert!(!is_necklace_slice(b"aba"));
+    assert!(!is_necklace_slice(b"aaba"));
+    assert!(!is_necklace_slice(b"ababa"));
     assert!(!is_necklace_slice(b"ba"));
     assert!(!is_necklace_slice(b"bab"));
     assert!(!is_necklace_slice(b"bba"));
@@ -543,9 +569,9 @@ fn reverse(c: u8) -> u8 {
 /// Online construction algorithm of
 /// J. Sawada, A. Williams and D. Wong. A surprisingly simple de Bruijn sequence
 /// construction. Discrete Math., 339(1):127--131, 2016.
-pub fn binary_debruijn_word(k: u8) -> Vec<u8> {
+pub fn binary_debruijn_word(k: usize) -> Vec<u8> {
     use std::collections::VecDeque;
-    let n = k as usize + 1;
+    let n = k + 1;
     let mut word: VecDeque<u8> = VecDeque::with_capacity(n);
     for _ in 0..n {
         word.push_back(CHR_A);
@@ -573,14 +599,16 @@ pub fn binary_debruijn_word(k: u8) -> Vec<u8> {
 /// The k-th binary de-Brujin sequence has a length of 2^{k+1}, and each substring of length k+1 is
 /// has a nuique occurrence
 #[test]
+#[allow(clippy::partialeq_to_none)]
 fn test_binary_debrujin_word() {
     use std::str;
 
     for k in 2..10 {
         let word = binary_debruijn_word(k);
+        assert_eq!(word.len(), 1 << (k + 1));
         let text = str::from_utf8(&word).unwrap();
-        for pos in 0..word.len() - k as usize {
-            let pattern = str::from_utf8(&word[pos..pos + 1 + k as usize]).unwrap();
+        for pos in 0..word.len() - k {
+            let pattern = str::from_utf8(&word[pos..pos + 1 + k]).unwrap();
             assert!(text[pos + 1..].find(pattern) == None);
         }
     }
@@ -591,7 +619,7 @@ fn test_binary_debrujin_word() {
 /// https://arxiv.org/pdf/2012.06840.pdf
 /// is https://oeis.org/A209229 and
 /// resembles https://oeis.org/A267366
-pub fn power2_sequence(k: u8) -> Vec<u8> {
+pub fn power2_sequence(k: usize) -> Vec<u8> {
     let n = 1 << k;
     let mut text = vec![0_u8; n];
     // let mut text : Vec<u8> = Vec::with_capacity(n);
@@ -610,6 +638,11 @@ pub fn power2_sequence(k: u8) -> Vec<u8> {
     text
 }
 
+/// streams the characteristic sequence of the powers of 2 computed by [`power2_sequence`]
+pub fn power2_letters() -> impl Iterator<Item = u8> {
+    (1_usize..).map(|j| if j.is_power_of_two() { CHR_B } else { CHR_A })
+}
+
 static STR_AC: &[u8] = &[CHR_A, CHR_C];
 
 pub fn tribonacci_morphism(c: u8) -> &'static [u8] {
@@ -620,7 +653,7 @@ pub fn tribonacci_morphism(c: u8) -> &'static [u8] {
     }
 }
 
-pub fn tribonacci_word(k: u8) -> Vec<u8> {
+pub fn tribonacci_word(k: usize) -> Vec<u8> {
     iterate_general_morphism(k, tribonacci_morphism, tribonacci_number(k))
 }
 
@@ -638,7 +671,7 @@ fn test_tribonacci() {
     );
 }
 
-pub fn tribonacci_number(k: u8) -> usize {
+pub fn tribonacci_number(k: usize) -> usize {
     if k == 0 {
         return 1;
     }
@@ -683,7 +716,7 @@ pub fn vtm_morphism(c: u8) -> &'static [u8] {
 /// A Thue-Morse variant studied in
 /// Francine Blanchet-Sadri, James D. Currie, Narad Rampersad, Nathan Fox:
 /// Abelian Complexity of Fixed Point of Morphism 0 ↦ 012, 1 ↦ 02, 2 ↦ 1. Integers 14: A11 (2014)
-pub fn vtm_word(k: u8) -> Vec<u8> {
+pub fn vtm_word(k: usize) -> Vec<u8> {
     iterate_general_morphism(k, vtm_morphism, (1 + (3 << (k))) as usize)
 }
 
@@ -707,7 +740,7 @@ pub fn fibonacci_lyndonfactor_morphism(c: u8) -> &'static [u8] {
 /// Guy Melançon:
 /// Lyndon Words and Singular Factors of Sturmian Words. Theor. Comput. Sci. 218(1): 41-59 (1999)
 /// Proposition 2.8
-pub fn fibonacci_lyndonfactor(k: u8) -> Vec<u8> {
+pub fn fibonacci_lyndonfactor(k: usize) -> Vec<u8> {
     iterate_general_morphism_with_startsymbol(
         k,
         fibonacci_lyndonfactor_morphism,
@@ -722,3 +755,1084 @@ fn test_fibonacci_lyndonfactor() {
     assert_eq!(b"aabab", fibonacci_lyndonfactor(1).as_slice());
     assert_eq!(b"aabaababaabab", fibonacci_lyndonfactor(2).as_slice());
 }
+
+/// A morphism on bytes given by the images of the letters of its domain, parsed from a
+/// specification like `a->ab,b->a`. The image of a letter may be empty (`c->`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Morphism {
+    images: Vec<Option<Vec<u8>>>, //@ the image of each byte, or None if the byte is not in the domain
+    letters: Vec<u8>,             //@ the domain in the order of the specification
+}
+
+impl Morphism {
+    /// Parses rules `letter->image` separated by commas, semicolons or line breaks,
+    /// where everything after a `#` up to the end of a line is a comment.
+    pub fn parse(spec: &str) -> Result<Morphism> {
+        let mut images = vec![None; 256];
+        let mut letters = Vec::new();
+        let rules = spec
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or_default())
+            .flat_map(|line| line.split([',', ';']))
+            .map(str::trim)
+            .filter(|rule| !rule.is_empty());
+        for rule in rules {
+            let (letter, image) = rule.split_once("->").ok_or_else(|| {
+                Error::InvalidArgument(format!("the rule '{}' is not of the form a->image", rule))
+            })?;
+            let letter = match letter.trim().as_bytes() {
+                [letter] => *letter,
+                _ => {
+                    return Err(Error::InvalidArgument(format!(
+                        "the rule '{}' does not map a single letter",
+                        rule
+                    )))
+                }
+            };
+            if images[letter as usize].is_some() {
+                return Err(Error::InvalidArgument(format!(
+                    "the letter '{}' has more than one image",
+                    letter as char
+                )));
+            }
+            images[letter as usize] = Some(image.trim().as_bytes().to_vec());
+            letters.push(letter);
+        }
+        if letters.is_empty() {
+            return Err(Error::InvalidArgument(String::from(
+                "the morphism has no rules",
+            )));
+        }
+        Ok(Morphism { images, letters })
+    }
+
+    /// parses the morphism specified in the file `filename`
+    pub fn from_file(filename: &str) -> Result<Morphism> {
+        let spec = std::fs::read_to_string(filename).map_err(|e| Error::file(filename, e))?;
+        Morphism::parse(&spec)
+    }
+
+    /// the morphism on the domain `letters` given by one of the morphism functions of this module
+    pub fn from_fn(letters: &[u8], morphism: fn(u8) -> &'static [u8]) -> Morphism {
+        let mut images = vec![None; 256];
+        for &letter in letters {
+            images[letter as usize] = Some(morphism(letter).to_vec());
+        }
+        Morphism {
+            images,
+            letters: letters.to_vec(),
+        }
+    }
+
+    /// the image of `letter`, or None if `letter` is not in the domain
+    pub fn image(&self, letter: u8) -> Option<&[u8]> {
+        self.images[letter as usize].as_deref()
+    }
+
+    /// the letters of the domain in the order of the specification
+    pub fn letters(&self) -> &[u8] {
+        &self.letters
+    }
+
+    /// whether each letter is mapped to a single letter, i.e., the morphism is a coding
+    pub fn is_coding(&self) -> bool {
+        self.letters
+            .iter()
+            .all(|&letter| self.images[letter as usize].as_ref().unwrap().len() == 1)
+    }
+
+    /// Whether the image of `letter` starts with `letter` and is longer than one letter,
+    /// such that iterating the morphism on `letter` converges to a fixed point.
+    pub fn is_prolongable(&self, letter: u8) -> bool {
+        matches!(self.image(letter), Some(image) if image.len() > 1 && image[0] == letter)
+    }
+
+    /// the letters of the domain that are not prolongable
+    pub fn non_prolongable_letters(&self) -> Vec<u8> {
+        self.letters
+            .iter()
+            .copied()
+            .filter(|&letter| !self.is_prolongable(letter))
+            .collect()
+    }
+
+    /// the image of `letter`, or an error naming `context` if `letter` is not in the domain
+    fn checked_image(&self, letter: u8, context: &str) -> Result<&[u8]> {
+        self.image(letter).ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "the letter '{}' {} has no image",
+                letter as char, context
+            ))
+        })
+    }
+
+    /// checks that the images only consist of letters of the domain
+    fn check_closed(&self) -> Result<()> {
+        for &letter in self.letters.iter() {
+            for &c in self.images[letter as usize].as_ref().unwrap() {
+                self.checked_image(c, &format!("in the image of '{}'", letter as char))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// the image of `text`, which must only consist of letters of the domain
+    pub fn apply(&self, text: &[u8]) -> Result<Vec<u8>> {
+        let mut image = Vec::with_capacity(text.len());
+        for &c in text {
+            image.extend_from_slice(self.checked_image(c, "of the text")?);
+        }
+        Ok(image)
+    }
+
+    /// the word obtained by applying the morphism `rounds` times to `start`
+    pub fn iterate(&self, start: &[u8], rounds: usize) -> Result<Vec<u8>> {
+        self.check_closed()?;
+        for &c in start {
+            self.checked_image(c, "to start with")?;
+        }
+        Ok(iterate_general_morphism_with_startsymbol(
+            rounds,
+            |c| self.image(c).unwrap(),
+            start.len(),
+            start,
+        ))
+    }
+
+    /// The prefix of length `length` of the fixed point starting with `start`, which requires
+    /// `start` to be prolongable and the fixed point to be infinite.
+    pub fn fixed_point(&self, start: u8, length: usize) -> Result<Vec<u8>> {
+        self.check_closed()?;
+        let image = self.checked_image(start, "to start with")?;
+        if !self.is_prolongable(start) {
+            return Err(Error::InvalidArgument(format!(
+                "the letter '{}' is not prolongable, since its image '{}' does not start with it or is not longer",
+                start as char,
+                String::from_utf8_lossy(image)
+            )));
+        }
+        let mut text: Vec<u8> = Vec::with_capacity(length);
+        text.extend_from_slice(image);
+        let mut source_pos = 1; //@ the image of the first letter is already written
+        while text.len() < length {
+            if source_pos == text.len() {
+                return Err(Error::InvalidArgument(format!(
+                    "the fixed point starting with '{}' is finite, of length {}",
+                    start as char,
+                    text.len()
+                )));
+            }
+            let c = text[source_pos];
+            text.extend_from_slice(self.image(c).unwrap());
+            source_pos += 1;
+        }
+        text.truncate(length);
+        Ok(text)
+    }
+}
+
+#[test]
+fn test_morphism_parse() {
+    let morphism = Morphism::parse("a->ab, b->a").unwrap();
+    assert_eq!(morphism.letters(), b"ab");
+    assert_eq!(morphism.image(b'a'), Some(&b"ab"[..]));
+    assert_eq!(morphism.image(b'c'), None);
+    assert_eq!(
+        Morphism::parse("# Thue-Morse\na->ab\nb->ba # swap\n").unwrap(),
+        Morphism::parse("a->ab;b->ba").unwrap()
+    );
+    assert_eq!(
+        Morphism::parse("a->ab,b->").unwrap().image(b'b'),
+        Some(&b""[..])
+    );
+    for spec in ["", "a->ab,a->b", "ab->a", "a=b", "->a"] {
+        assert!(matches!(
+            Morphism::parse(spec),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+    assert!(Morphism::parse("a->0,b->1").unwrap().is_coding());
+    assert!(!Morphism::parse("a->01,b->1").unwrap().is_coding());
+}
+
+#[test]
+fn test_morphism_fixed_point() {
+    let fibonacci = Morphism::parse("a->ab,b->a").unwrap();
+    for k in 0..10 {
+        let word = fibonacci_word(k);
+        assert_eq!(fibonacci.fixed_point(b'a', word.len()).unwrap(), word);
+        assert_eq!(fibonacci.iterate(b"a", k).unwrap(), word);
+    }
+    let vtm = Morphism::parse("a->abc,b->ac,c->b").unwrap();
+    assert_eq!(vtm.fixed_point(b'a', 24).unwrap(), vtm_word(4));
+    assert_eq!(
+        Morphism::parse("a->aab,b->ab")
+            .unwrap()
+            .iterate(b"ab", 2)
+            .unwrap(),
+        fibonacci_lyndonfactor(2)
+    );
+
+    //@ b is mapped to a word not starting with b, and c has a finite fixed point
+    let morphism = Morphism::parse("a->ab,b->a,c->cd,d->").unwrap();
+    assert_eq!(morphism.non_prolongable_letters(), b"bd");
+    assert!(morphism.fixed_point(b'b', 10).is_err());
+    assert!(morphism.fixed_point(b'c', 10).is_err());
+    assert_eq!(morphism.fixed_point(b'c', 2).unwrap(), b"cd");
+    assert_eq!(morphism.iterate(b"b", 3).unwrap(), b"aba");
+    assert!(Morphism::parse("a->ab")
+        .unwrap()
+        .fixed_point(b'a', 5)
+        .is_err());
+
+    let coding = Morphism::parse("a->0,b->1").unwrap();
+    assert_eq!(coding.apply(b"abaab").unwrap(), b"01001");
+    assert!(coding.apply(b"abc").is_err());
+}
+
+/// The standard word s_n of the directive sequence d_0, ..., d_{n-1}, where s_{-1} = b, s_0 = a,
+/// and s_{k+1} = s_k^{d_k} s_{k-1}. These are the prefixes of the characteristic Sturmian word
+/// of slope [0; d_0 + 1, d_1, d_2, ...] (for d_0 ≥ 1), and the all-ones sequence yields the
+/// Fibonacci words. Only d_0 may be zero.
+pub fn standard_word(directive: &[usize]) -> Result<Vec<u8>> {
+    if let Some(position) = directive.iter().skip(1).position(|&d| d == 0) {
+        return Err(Error::InvalidArgument(format!(
+            "the directive sequence has a zero at position {}",
+            position + 1
+        )));
+    }
+    let mut previous = vec![CHR_B];
+    let mut current = vec![CHR_A];
+    for &d in directive {
+        let mut next = Vec::with_capacity(d * current.len() + previous.len());
+        for _ in 0..d {
+            next.extend_from_slice(&current);
+        }
+        next.extend_from_slice(&previous);
+        previous = current;
+        current = next;
+    }
+    Ok(current)
+}
+
+#[test]
+fn test_standard_word() {
+    for k in 0..12 {
+        assert_eq!(standard_word(&vec![1; k]).unwrap(), fibonacci_word(k));
+    }
+    assert_eq!(standard_word(&[2]).unwrap(), b"aab");
+    assert_eq!(standard_word(&[2, 3]).unwrap(), b"aabaabaaba");
+    assert_eq!(standard_word(&[0, 2]).unwrap(), b"bba");
+    assert!(standard_word(&[1, 0]).is_err());
+}
+
+/// The prefix of length `length` of the lower mechanical word of slope α = `slope` and
+/// intercept ρ = `intercept`, whose n-th letter is b if ⌊(n+1)α + ρ⌋ - ⌊nα + ρ⌋ = 1 and a
+/// otherwise, or of the upper mechanical word if `upper`, where ⌈⋅⌉ replaces ⌊⋅⌋.
+/// The word is Sturmian for an irrational slope; the letters are computed in floating point.
+pub fn mechanical_word(slope: f64, intercept: f64, length: usize, upper: bool) -> Result<Vec<u8>> {
+    if !(0.0..=1.0).contains(&slope) {
+        return Err(Error::InvalidArgument(format!(
+            "the slope must be in [0, 1], but is {}",
+            slope
+        )));
+    }
+    let round = if upper { f64::ceil } else { f64::floor };
+    Ok((0..length)
+        .map(|n| {
+            let x = n as f64 * slope + intercept;
+            if round(x + slope) > round(x) {
+                CHR_B
+            } else {
+                CHR_A
+            }
+        })
+        .collect())
+}
+
+/// the prefix of length `length` of the characteristic word of slope `slope`, which is the
+/// mechanical word with the intercept equal to the slope
+pub fn characteristic_word(slope: f64, length: usize) -> Result<Vec<u8>> {
+    mechanical_word(slope, slope, length, false)
+}
+
+#[test]
+fn test_mechanical_word() {
+    //@ the Fibonacci word is the characteristic word of slope 1/φ²
+    let fibonacci = fibonacci_word(12);
+    let slope = 1.0 / (GOLDEN_RATIO * GOLDEN_RATIO);
+    assert_eq!(
+        characteristic_word(slope, fibonacci.len()).unwrap(),
+        fibonacci
+    );
+    assert_eq!(mechanical_word(0.5, 0.0, 6, false).unwrap(), b"ababab");
+    assert_eq!(mechanical_word(0.5, 0.0, 6, true).unwrap(), b"bababa");
+    assert_eq!(mechanical_word(0.0, 0.3, 3, false).unwrap(), b"aaa");
+    assert_eq!(mechanical_word(1.0, 0.3, 3, true).unwrap(), b"bbb");
+    assert!(mechanical_word(1.5, 0.0, 3, false).is_err());
+    //@ the lower and upper mechanical words of intercept 0 are a and b followed by the characteristic word
+    let lower = mechanical_word(slope, 0.0, 100, false).unwrap();
+    let upper = mechanical_word(slope, 0.0, 100, true).unwrap();
+    assert_eq!(lower[0], CHR_A);
+    assert_eq!(upper[0], CHR_B);
+    assert_eq!(lower[1..], upper[1..]);
+    assert_eq!(lower[1..], characteristic_word(slope, 99).unwrap());
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The lower Christoffel word of slope p/q, which has q letters a and p letters b, and whose
+/// i-th letter is b if ⌊(i+1)p/(p+q)⌋ - ⌊ip/(p+q)⌋ = 1, or the upper Christoffel word if
+/// `upper`, which is the reverse of the lower one. `p` and `q` must be coprime.
+pub fn christoffel_word(p: usize, q: usize, upper: bool) -> Result<Vec<u8>> {
+    if gcd(p, q) != 1 {
+        return Err(Error::InvalidArgument(format!(
+            "the numerator {} and the denominator {} of the slope must be coprime",
+            p, q
+        )));
+    }
+    let n = (p + q) as u128;
+    let p = p as u128;
+    let mut word: Vec<u8> = (0..n)
+        .map(|i| {
+            if ((i + 1) * p) / n > (i * p) / n {
+                CHR_B
+            } else {
+                CHR_A
+            }
+        })
+        .collect();
+    if upper {
+        word.reverse();
+    }
+    Ok(word)
+}
+
+#[test]
+fn test_christoffel_word() {
+    assert_eq!(christoffel_word(2, 3, false).unwrap(), b"aabab");
+    assert_eq!(christoffel_word(2, 3, true).unwrap(), b"babaa");
+    assert_eq!(christoffel_word(0, 1, false).unwrap(), b"a");
+    assert_eq!(christoffel_word(1, 0, false).unwrap(), b"b");
+    assert_eq!(christoffel_word(1, 1, false).unwrap(), b"ab");
+    assert!(christoffel_word(2, 4, false).is_err());
+    assert!(christoffel_word(0, 0, false).is_err());
+    //@ the lower Christoffel word is the lower mechanical word of slope p/(p+q)
+    for (p, q) in [(3, 5), (5, 8), (7, 3), (1, 9)] {
+        let slope = p as f64 / (p + q) as f64;
+        assert_eq!(
+            christoffel_word(p, q, false).unwrap(),
+            mechanical_word(slope, 0.0, p + q, false).unwrap()
+        );
+        let lower = christoffel_word(p, q, false).unwrap();
+        //@ a lower Christoffel word is a, followed by a palindrome, followed by b
+        let central = &lower[1..lower.len() - 1];
+        assert!(central.iter().eq(central.iter().rev()));
+    }
+}
+
+/// The palindromic prefix u_n of the standard episturmian word with the directive sequence
+/// x_1, ..., x_n, where u_0 is empty and u_{i+1} is the palindromic closure of u_i x_{i+1}.
+/// It is computed with Justin's formula u_{i+1} = u_i x_{i+1} u_i if x_{i+1} does not occur in
+/// u_i, and u_{i+1} = u_i u_m^{-1} u_i otherwise, where m is the last index with x_{m+1} = x_{i+1}.
+/// Jacques Justin: Episturmian morphisms and a Galois theorem on continued fractions.
+/// RAIRO Theor. Informatics Appl. 39(1): 207-215 (2005)
+pub fn episturmian_word(directive: &[u8]) -> Vec<u8> {
+    episturmian_prefix(directive.iter().copied(), usize::MAX)
+}
+
+/// The prefix of length `prefix_length` of the standard episturmian word with the (possibly
+/// infinite) directive sequence `directive`, computed like [`episturmian_word`] up to the first
+/// palindromic prefix that is long enough, or the palindromic prefix of a finite directive sequence
+/// if shorter.
+pub fn episturmian_prefix(
+    directive: impl IntoIterator<Item = u8>,
+    prefix_length: usize,
+) -> Vec<u8> {
+    let mut text: Vec<u8> = Vec::new();
+    let mut last_length = [None; 256]; //@ the length of u_m for the last m with x_{m+1} = c
+    for c in directive {
+        if text.len() >= prefix_length {
+            break;
+        }
+        let length = text.len();
+        match last_length[c as usize] {
+            None => {
+                text.push(c);
+                text.extend_from_within(..length);
+            }
+            Some(prefix_length) => text.extend_from_within(prefix_length..length),
+        }
+        last_length[c as usize] = Some(length);
+    }
+    text.truncate(prefix_length);
+    text
+}
+
+/// The palindromic prefix of the Arnoux-Rauzy word over the first `sigma` letters a, b, ...
+/// with the directive sequence `directive`, in which each of these letters has to occur.
+pub fn arnoux_rauzy_word(directive: &[u8], sigma: usize) -> Result<Vec<u8>> {
+    check_arnoux_rauzy_directive(directive, sigma)?;
+    Ok(episturmian_word(directive))
+}
+
+/// checks that the directive sequence consists of the first `sigma` letters a, b, ..., each of
+/// which occurs
+pub fn check_arnoux_rauzy_directive(directive: &[u8], sigma: usize) -> Result<()> {
+    let mut occurs = vec![false; sigma];
+    for &c in directive {
+        match c.checked_sub(CHR_A).filter(|&rank| (rank as usize) < sigma) {
+            Some(rank) => occurs[rank as usize] = true,
+            None => {
+                return Err(Error::InvalidArgument(format!(
+                    "the letter '{}' of the directive sequence is not among the first {} letters",
+                    c as char, sigma
+                )))
+            }
+        }
+    }
+    if let Some(rank) = occurs.iter().position(|&occurs| !occurs) {
+        return Err(Error::InvalidArgument(format!(
+            "the letter '{}' does not occur in the directive sequence",
+            (CHR_A + rank as u8) as char
+        )));
+    }
+    Ok(())
+}
+
+/// The k-th k-bonacci word of the given `order`, which is the fixed point of the morphism
+/// a -> ab, b -> ac, ..., mapping the last letter of the `order` letters to a.
+/// The orders 2 and 3 yield the Fibonacci and Tribonacci words.
+pub fn kbonacci_word(order: usize, k: usize) -> Result<Vec<u8>> {
+    let morphism = kbonacci_morphism(order)?;
+    Ok(iterate_general_morphism(
+        k,
+        |c| morphism.image(c).unwrap(),
+        1,
+    ))
+}
+
+/// the morphism a -> ab, b -> ac, ... of the given `order`, whose fixed point is the k-bonacci word
+pub fn kbonacci_morphism(order: usize) -> Result<Morphism> {
+    if !(2..=26).contains(&order) {
+        return Err(Error::InvalidArgument(format!(
+            "the order of a k-bonacci word must be between 2 and 26, but is {}",
+            order
+        )));
+    }
+    let letters: Vec<u8> = (0..order as u8).map(|rank| CHR_A + rank).collect();
+    let mut images = vec![None; 256];
+    for (rank, &letter) in letters.iter().enumerate() {
+        images[letter as usize] = Some(match letters.get(rank + 1) {
+            Some(&next) => vec![CHR_A, next],
+            None => vec![CHR_A],
+        });
+    }
+    Ok(Morphism { images, letters })
+}
+
+#[test]
+fn test_kbonacci_word() {
+    for k in 0..12 {
+        assert_eq!(kbonacci_word(2, k).unwrap(), fibonacci_word(k));
+        assert_eq!(kbonacci_word(3, k).unwrap(), tribonacci_word(k));
+    }
+    assert_eq!(kbonacci_word(4, 3).unwrap(), b"abacabad");
+    assert!(kbonacci_word(1, 3).is_err());
+    //@ the fixed point streams prefixes of the k-bonacci words
+    for order in 2..6 {
+        let letters = kbonacci_morphism(order)
+            .unwrap()
+            .fixed_point_letters(CHR_A)
+            .unwrap();
+        let prefix: Vec<u8> = letters.take(100).collect();
+        assert_eq!(prefix, &kbonacci_word(order, 12).unwrap()[..100]);
+    }
+}
+
+#[test]
+fn test_episturmian_word() {
+    assert_eq!(episturmian_word(b""), b"");
+    assert_eq!(episturmian_word(b"a"), b"a");
+    assert_eq!(episturmian_word(b"ab"), b"aba");
+    assert_eq!(episturmian_word(b"aba"), b"abaaba");
+    assert_eq!(episturmian_word(b"abca"), b"abacabaabacaba");
+    assert_eq!(episturmian_prefix(b"abca".iter().copied(), 9), b"abacabaab");
+    assert_eq!(episturmian_prefix(b"ab".iter().copied(), 9), b"aba");
+    //@ the periodic directive sequence abab... gives the Fibonacci word
+    assert_eq!(
+        episturmian_prefix(b"ab".iter().copied().cycle(), 100),
+        &fibonacci_word(12)[..100]
+    );
+
+    //@ the palindromic prefixes of the k-bonacci words are the prefixes of the standard episturmian words with periodic directive sequence
+    for order in 2..6 {
+        let directive: Vec<u8> = (0..order as u8)
+            .map(|rank| CHR_A + rank)
+            .cycle()
+            .take(12)
+            .collect();
+        let word = arnoux_rauzy_word(&directive, order).unwrap();
+        let kbonacci = kbonacci_word(order, 20).unwrap();
+        assert_eq!(word, &kbonacci[..word.len()]);
+    }
+    //@ the palindromic closure of u_i x_{i+1} is the shortest palindrome with this prefix
+    let directive = b"abcbbacabcc";
+    for i in 0..directive.len() {
+        let prefix = episturmian_word(&directive[..i]);
+        let closure = episturmian_word(&directive[..=i]);
+        assert!(closure.iter().eq(closure.iter().rev()));
+        assert!(closure.starts_with(&prefix));
+        assert_eq!(closure[prefix.len()], directive[i]);
+        let mut shorter = prefix.clone();
+        shorter.push(directive[i]);
+        let shortest = (shorter.len()..closure.len()).find(|&length| {
+            (length - shorter.len()..shorter.len()).all(|j| shorter[j] == shorter[length - 1 - j])
+        });
+        assert_eq!(shortest, None);
+    }
+    assert!(arnoux_rauzy_word(b"abab", 3).is_err());
+    assert!(arnoux_rauzy_word(b"abcd", 3).is_err());
+}
+
+#[test]
+fn test_arnoux_rauzy_complexity() {
+    use std::collections::HashSet;
+    //@ an Arnoux-Rauzy word over k letters has (k-1)n+1 distinct factors of length n
+    for (directive, sigma) in [
+        (b"abcabcabcabcabc".to_vec(), 3),
+        (b"aabbccabacbcab".to_vec(), 3),
+        (b"abcdabcdabcdabcd".to_vec(), 4),
+        (b"abacadbcdbcaddcab".to_vec(), 4),
+    ] {
+        let word = arnoux_rauzy_word(&directive, sigma).unwrap();
+        for n in 1..20 {
+            let factors: HashSet<&[u8]> = word.windows(n).collect();
+            assert_eq!(factors.len(), (sigma - 1) * n + 1);
+        }
+    }
+}
+
+/// A deterministic finite automaton with output (DFAO) generating a k-automatic sequence,
+/// whose n-th letter is the output of the state reached from the initial state 0 by reading
+/// the base-k representation of n without leading zeros, most significant digit first.
+/// Jean-Paul Allouche, Jeffrey Shallit: Automatic Sequences. Cambridge University Press (2003)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dfao {
+    base: usize,
+    transitions: Vec<Vec<usize>>, //@ transitions[q][d] is the state reached from q by reading d
+    outputs: Vec<u8>,             //@ the letter output in each state
+}
+
+impl Dfao {
+    /// The DFAO reading base-`base` digits with the given transitions and outputs per state,
+    /// such that `transitions[q][d]` is the state reached from state q by the digit d.
+    pub fn new(base: usize, transitions: Vec<Vec<usize>>, outputs: Vec<u8>) -> Result<Dfao> {
+        if base < 2 {
+            return Err(Error::InvalidArgument(format!(
+                "the base must be at least 2, but is {}",
+                base
+            )));
+        }
+        if outputs.is_empty() || transitions.len() != outputs.len() {
+            return Err(Error::InvalidArgument(format!(
+                "the DFAO has {} states with transitions, but {} states with outputs",
+                transitions.len(),
+                outputs.len()
+            )));
+        }
+        for (state, targets) in transitions.iter().enumerate() {
+            if targets.len() != base || targets.iter().any(|&target| target >= outputs.len()) {
+                return Err(Error::InvalidArgument(format!(
+                    "the state {} needs a transition to a state for each of the {} digits",
+                    state, base
+                )));
+            }
+        }
+        Ok(Dfao {
+            base,
+            transitions,
+            outputs,
+        })
+    }
+
+    /// the n-th letter of the sequence
+    pub fn letter(&self, n: usize) -> u8 {
+        let mut digits = Vec::new();
+        let mut remainder = n;
+        while remainder > 0 {
+            digits.push(remainder % self.base);
+            remainder /= self.base;
+        }
+        let state = digits
+            .iter()
+            .rev()
+            .fold(0, |state, &digit| self.transitions[state][digit]);
+        self.outputs[state]
+    }
+
+    /// the prefix of length `length` of the sequence
+    pub fn word(&self, length: usize) -> Vec<u8> {
+        (0..length).map(|n| self.letter(n)).collect()
+    }
+
+    /// The generalized Thue-Morse sequence over `sigma` letters, whose n-th letter is the sum of
+    /// the base-`sigma` digits of n modulo `sigma`.
+    pub fn thue_morse(sigma: usize) -> Result<Dfao> {
+        if !(2..=26).contains(&sigma) {
+            return Err(Error::InvalidArgument(format!(
+                "the alphabet size must be between 2 and 26, but is {}",
+                sigma
+            )));
+        }
+        let transitions = (0..sigma)
+            .map(|state| (0..sigma).map(|digit| (state + digit) % sigma).collect())
+            .collect();
+        let outputs = (0..sigma as u8).map(|rank| CHR_A + rank).collect();
+        Dfao::new(sigma, transitions, outputs)
+    }
+
+    /// The Rudin-Shapiro sequence https://oeis.org/A020987 with a for 0 and b for 1, whose n-th
+    /// letter is the parity of the number of (possibly overlapping) blocks 11 in the binary
+    /// representation of n. A state is the parity and the last bit read.
+    pub fn rudin_shapiro() -> Dfao {
+        Dfao::new(
+            2,
+            vec![vec![0, 1], vec![0, 3], vec![2, 3], vec![2, 1]],
+            vec![CHR_A, CHR_A, CHR_B, CHR_B],
+        )
+        .unwrap()
+    }
+
+    /// The Baum-Sweet sequence https://oeis.org/A086747 with a for 0 and b for 1, whose n-th
+    /// letter is b if the binary representation of n has no block of zeros of odd length.
+    /// The states are: no open block of zeros, an open block of odd length, an open block of
+    /// even length, and a closed block of odd length.
+    pub fn baum_sweet() -> Dfao {
+        Dfao::new(
+            2,
+            vec![vec![1, 0], vec![2, 3], vec![1, 0], vec![3, 3]],
+            vec![CHR_B, CHR_A, CHR_B, CHR_A],
+        )
+        .unwrap()
+    }
+
+    /// The characteristic sequence of the Cantor set https://oeis.org/A088917 with a for 0 and
+    /// b for 1, whose n-th letter is b if the ternary representation of n has no digit 1.
+    pub fn cantor() -> Dfao {
+        Dfao::new(3, vec![vec![0, 1, 0], vec![1, 1, 1]], vec![CHR_B, CHR_A]).unwrap()
+    }
+
+    /// Stewart's choral sequence https://oeis.org/A116178 with a for 0 and b for 1, given by
+    /// c(3n) = 0, c(3n+1) = c(n), and c(3n+2) = 1, such that the n-th letter is determined by the
+    /// last ternary digit of n that is not 1, which is stored in the state.
+    pub fn stewart_choral() -> Dfao {
+        Dfao::new(3, vec![vec![0, 0, 1], vec![0, 1, 1]], vec![CHR_A, CHR_B]).unwrap()
+    }
+
+    /// The Mephisto Waltz sequence https://oeis.org/A064990 with a for 0 and b for 1, the fixed
+    /// point of a -> aab, b -> bba, whose n-th letter is the parity of the number of digits 2 in
+    /// the ternary representation of n.
+    pub fn mephisto_waltz() -> Dfao {
+        Dfao::new(3, vec![vec![0, 0, 1], vec![1, 1, 0]], vec![CHR_A, CHR_B]).unwrap()
+    }
+}
+
+#[test]
+fn test_dfao() {
+    let ternary = |mut n: usize| {
+        let mut digits = Vec::new();
+        while n > 0 {
+            digits.push((n % 3) as u8);
+            n /= 3;
+        }
+        digits
+    };
+    let letter = |condition: bool| if condition { CHR_B } else { CHR_A };
+
+    for k in 0..10 {
+        assert_eq!(Dfao::thue_morse(2).unwrap().word(1 << k), thuemorse_word(k));
+    }
+    let thue_morse = Dfao::thue_morse(3).unwrap();
+    assert_eq!(thue_morse.word(9), b"abcbcacab");
+    assert!(Dfao::thue_morse(1).is_err());
+
+    assert_eq!(Dfao::rudin_shapiro().word(16), b"aaabaabaaaabbbab");
+    assert_eq!(Dfao::baum_sweet().word(16), b"bbabbaababaabaab");
+    assert_eq!(Dfao::cantor().word(10), b"babaaababa");
+    assert_eq!(Dfao::stewart_choral().word(18), b"aabaababbaabaababb");
+    assert_eq!(Dfao::mephisto_waltz().word(9), b"aabaabbba");
+    for n in 0..1000 {
+        assert_eq!(
+            Dfao::rudin_shapiro().letter(n),
+            letter((n & (n >> 1)).count_ones() % 2 == 1)
+        );
+        let odd_zero_block = n > 0
+            && format!("{:b}", n)
+                .split('1')
+                .any(|block| block.len() % 2 == 1);
+        assert_eq!(Dfao::baum_sweet().letter(n), letter(!odd_zero_block));
+        assert_eq!(Dfao::cantor().letter(n), letter(!ternary(n).contains(&1)));
+        assert_eq!(
+            Dfao::mephisto_waltz().letter(n),
+            letter(ternary(n).iter().filter(|&&digit| digit == 2).count() % 2 == 1)
+        );
+        let choral = match n % 3 {
+            0 => CHR_A,
+            1 => Dfao::stewart_choral().letter(n / 3),
+            _ => CHR_B,
+        };
+        assert_eq!(Dfao::stewart_choral().letter(n), choral);
+    }
+    assert_eq!(
+        Dfao::mephisto_waltz().word(729),
+        Morphism::parse("a->aab,b->bba")
+            .unwrap()
+            .fixed_point(CHR_A, 729)
+            .unwrap()
+    );
+    assert_eq!(
+        Dfao::cantor().word(729),
+        Morphism::parse("b->bab,a->aaa")
+            .unwrap()
+            .fixed_point(CHR_B, 729)
+            .unwrap()
+    );
+    assert!(Dfao::new(2, vec![vec![0, 1]], vec![CHR_A]).is_err());
+    assert!(Dfao::new(2, vec![vec![0]], vec![CHR_A]).is_err());
+    assert!(Dfao::new(1, vec![vec![0]], vec![CHR_A]).is_err());
+}
+
+/// The lexicographically smallest de Bruijn sequence of order `k` over the first `sigma`
+/// letters a, b, ..., which has length sigma^k and contains each word of length k exactly once
+/// as a cyclic substring. It is the concatenation of the Lyndon words whose length divides k
+/// in lexicographic order.
+/// Harold Fredricksen, James Maiorana: Necklaces of beads in k colors and k-ary de Bruijn
+/// sequences. Discret. Math. 23(3): 207-210 (1978)
+pub fn debruijn_word(sigma: usize, k: usize) -> Result<Vec<u8>> {
+    if !(1..=26).contains(&sigma) || k == 0 {
+        return Err(Error::InvalidArgument(format!(
+            "a de Bruijn sequence needs an alphabet size between 1 and 26 and a positive order, but got {} and {}",
+            sigma, k
+        )));
+    }
+    let length = u32::try_from(k)
+        .ok()
+        .and_then(|k| sigma.checked_pow(k))
+        .ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "the de Bruijn sequence of order {} over {} letters is too long",
+                k, sigma
+            ))
+        })?;
+    let mut text = Vec::with_capacity(length);
+    for lyndon_word in crate::core::LyndonWordGenerator::new(k, sigma) {
+        if k.is_multiple_of(lyndon_word.len()) {
+            text.extend(lyndon_word.iter().map(|&rank| CHR_A + rank));
+        }
+    }
+    Ok(text)
+}
+
+/// The de Bruijn sequence of [`debruijn_word`] extended cyclically by k-1 letters, such that
+/// it contains each word of length k exactly once as a substring.
+pub fn linear_debruijn_word(sigma: usize, k: usize) -> Result<Vec<u8>> {
+    let mut text = debruijn_word(sigma, k)?;
+    let period = text.len();
+    for i in 0..k - 1 {
+        text.push(text[i % period]);
+    }
+    Ok(text)
+}
+
+#[test]
+fn test_debruijn_word() {
+    use std::collections::HashSet;
+    assert_eq!(debruijn_word(2, 3).unwrap(), b"aaababbb");
+    assert_eq!(debruijn_word(3, 2).unwrap(), b"aabacbbcc");
+    assert_eq!(debruijn_word(1, 4).unwrap(), b"a");
+    assert_eq!(linear_debruijn_word(1, 4).unwrap(), b"aaaa");
+    assert_eq!(linear_debruijn_word(2, 3).unwrap(), b"aaababbbaa");
+    assert!(debruijn_word(0, 2).is_err());
+    assert!(debruijn_word(2, 0).is_err());
+    assert!(debruijn_word(26, 100).is_err());
+    for sigma in 1..5 {
+        for k in 1..6 {
+            let linear = linear_debruijn_word(sigma, k).unwrap();
+            assert_eq!(linear.len(), sigma.pow(k as u32) + k - 1);
+            let factors: HashSet<&[u8]> = linear.windows(k).collect();
+            assert_eq!(factors.len(), sigma.pow(k as u32));
+            assert!(factors
+                .iter()
+                .all(|factor| factor.iter().all(|&c| c < CHR_A + sigma as u8)));
+        }
+    }
+    //@ the binary de Bruijn sequence of Sawada et al. has the same factors
+    for k in 2..10 {
+        let mut word = binary_debruijn_word(k);
+        word.extend_from_within(..k);
+        let factors: HashSet<&[u8]> = word.windows(k + 1).collect();
+        let linear = linear_debruijn_word(2, k + 1).unwrap();
+        let expected: HashSet<&[u8]> = linear.windows(k + 1).collect();
+        assert_eq!(factors, expected);
+    }
+}
+
+/// Streams the fixed point of a morphism starting with a prolongable letter x, letter by letter.
+/// Since the fixed point w satisfies w = h(w), its letters are the concatenation of the images
+/// of its own letters, which are streamed by a second generator lagging behind, and so on.
+/// These generators are kept as a stack of levels, each storing only the image it currently
+/// emits, such that only logarithmically many levels are needed for morphisms whose images grow
+/// exponentially.
+pub struct FixedPointLetters {
+    morphism: Morphism,
+    start: u8,
+    levels: Vec<(u8, usize)>, //@ the letter whose image a level emits, and the position of the next letter in it
+    remaining: Option<std::vec::IntoIter<u8>>, //@ the letters of a finite fixed point
+}
+
+impl Morphism {
+    /// The letters whose iterated images eventually become empty, which are those whose image
+    /// consists only of such letters.
+    fn mortal_letters(&self) -> Vec<bool> {
+        let mut mortal = vec![false; 256];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &letter in self.letters.iter() {
+                if !mortal[letter as usize]
+                    && self.images[letter as usize]
+                        .as_ref()
+                        .unwrap()
+                        .iter()
+                        .all(|&c| mortal[c as usize])
+                {
+                    mortal[letter as usize] = true;
+                    changed = true;
+                }
+            }
+        }
+        mortal
+    }
+
+    /// The letters of the fixed point starting with the prolongable letter `start`, streamed
+    /// without storing the fixed point. The iterator ends if the fixed point is finite.
+    pub fn fixed_point_letters(&self, start: u8) -> Result<FixedPointLetters> {
+        self.fixed_point(start, 0)?; //@ checks the morphism and that `start` is prolongable
+        let image = self.image(start).unwrap();
+        let mortal = self.mortal_letters();
+        let remaining = if image[1..].iter().all(|&c| mortal[c as usize]) {
+            //@ the fixed point is finite, since all letters after the first are mortal
+            let mut text = image.to_vec();
+            let mut source_pos = 1;
+            while source_pos < text.len() {
+                let c = text[source_pos];
+                text.extend_from_slice(self.image(c).unwrap());
+                source_pos += 1;
+            }
+            Some(text.into_iter())
+        } else {
+            None
+        };
+        Ok(FixedPointLetters {
+            morphism: self.clone(),
+            start,
+            levels: vec![(start, 0)],
+            remaining,
+        })
+    }
+}
+
+impl FixedPointLetters {
+    /// whether the fixed point is finite, such that the iterator ends
+    pub fn is_finite(&self) -> bool {
+        self.remaining.is_some()
+    }
+}
+
+impl Iterator for FixedPointLetters {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if let Some(remaining) = self.remaining.as_mut() {
+            return remaining.next();
+        }
+        let mut level = 0;
+        loop {
+            if level == self.levels.len() {
+                //@ a new level emits the fixed point without its first letter, whose image is emitted by the level above
+                self.levels.push((self.start, 1));
+            }
+            let (letter, position) = &mut self.levels[level];
+            let image = self.morphism.image(*letter).unwrap();
+            if *position < image.len() {
+                let c = image[*position];
+                *position += 1;
+                if level == 0 {
+                    return Some(c);
+                }
+                level -= 1;
+                self.levels[level] = (c, 0);
+            } else {
+                level += 1;
+            }
+        }
+    }
+}
+
+/// Streams the Kolakoski word starting with bb, as computed by [`kolakoski_word`], letter by
+/// letter, where the letter a stands for 1 and b for 2. The word consists of runs alternating
+/// between b and a, whose lengths are given by the word itself. Like [`FixedPointLetters`], it
+/// reads the run lengths from a stack of lagging generators, each knowing the first two runs bb
+/// and aa.
+/// Johan Nilsson: A space-efficient algorithm for calculating the digit distribution in the
+/// Kolakoski sequence. J. Integer Seq. 15(6): 12.6.7 (2012)
+pub struct KolakoskiLetters {
+    levels: Vec<(usize, usize)>, //@ the index of the run a level emits, and the number of its remaining letters
+}
+
+impl KolakoskiLetters {
+    pub fn new() -> KolakoskiLetters {
+        KolakoskiLetters {
+            levels: vec![(0, 2)],
+        }
+    }
+}
+
+impl Default for KolakoskiLetters {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Iterator for KolakoskiLetters {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let mut level = 0;
+        loop {
+            if level == self.levels.len() {
+                //@ a new level emits the word from its third letter on, i.e., from the run aa on
+                self.levels.push((1, 2));
+            }
+            let (run, remaining) = &mut self.levels[level];
+            if *remaining > 0 {
+                *remaining -= 1;
+                let c = if *run % 2 == 0 { CHR_B } else { CHR_A };
+                if level == 0 {
+                    return Some(c);
+                }
+                level -= 1;
+                let (run, remaining) = &mut self.levels[level];
+                *run += 1;
+                *remaining = if c == CHR_B { 2 } else { 1 };
+            } else if *run == 0 {
+                //@ the length of the second run is the second letter b, which is not yet read
+                *run = 1;
+                *remaining = 2;
+            } else {
+                level += 1;
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fixed_point_letters() {
+    let fibonacci = Morphism::parse("a->ab,b->a").unwrap();
+    let word = fibonacci_word(20);
+    assert!(fibonacci
+        .fixed_point_letters(CHR_A)
+        .unwrap()
+        .take(word.len())
+        .eq(word.iter().copied()));
+    //@ the number of levels grows logarithmically
+    let mut letters = fibonacci.fixed_point_letters(CHR_A).unwrap();
+    assert_eq!(
+        letters.nth(1_000_000),
+        Some(fibonacci.fixed_point(CHR_A, 1_000_001).unwrap()[1_000_000])
+    );
+    assert!(letters.levels.len() < 40);
+
+    for spec in [
+        "a->abc,b->ac,c->b",
+        "a->aab,b->bba",
+        "a->ab,b->bb",
+        "a->aca,b->,c->bcb",
+        "a->ab,b->cb,c->",
+    ] {
+        let morphism = Morphism::parse(spec).unwrap();
+        let expected = morphism.fixed_point(CHR_A, 1000).unwrap();
+        assert!(
+            morphism
+                .fixed_point_letters(CHR_A)
+                .unwrap()
+                .take(1000)
+                .eq(expected.into_iter()),
+            "{}",
+            spec
+        );
+    }
+    //@ finite fixed points end
+    let finite = Morphism::parse("a->abc,b->c,c->").unwrap();
+    assert!(finite
+        .fixed_point_letters(CHR_A)
+        .unwrap()
+        .eq(b"abcc".iter().copied()));
+    assert!(Morphism::parse("a->ba,b->a")
+        .unwrap()
+        .fixed_point_letters(CHR_A)
+        .is_err());
+}
+
+#[test]
+fn test_named_letters() {
+    let quaternary = quaternary_paperfolding_word(10);
+    assert!(quaternary_paperfolding_letters()
+        .take(quaternary.len())
+        .eq(quaternary.into_iter()));
+    let binary = paperfolding_word(10);
+    assert!(paperfolding_letters()
+        .take(binary.len())
+        .eq(binary.into_iter()));
+    let power2 = power2_sequence(10);
+    assert!(power2_letters()
+        .take(power2.len() - 1)
+        .eq(power2[..power2.len() - 1].iter().copied()));
+    for (letters, morphism, word) in [
+        (
+            &b"ab"[..],
+            fibonacci_morphism as fn(u8) -> &'static [u8],
+            fibonacci_word(15),
+        ),
+        (b"ab", thuemorse_morphism, thuemorse_word(10)),
+        (b"ab", period_doubling_morphism, period_doubling_word(10)),
+        (b"abc", tribonacci_morphism, tribonacci_word(10)),
+        (b"abc", vtm_morphism, vtm_word(10)),
+    ] {
+        assert!(Morphism::from_fn(letters, morphism)
+            .fixed_point_letters(CHR_A)
+            .unwrap()
+            .take(word.len())
+            .eq(word.into_iter()));
+    }
+}
+
+#[test]
+fn test_kolakoski_letters() {
+    for k in 0..20 {
+        let word = kolakoski_word(k);
+        assert!(KolakoskiLetters::new()
+            .take(word.len())
+            .eq(word.iter().copied()));
+    }
+    let mut letters = KolakoskiLetters::new();
+    letters.nth(1_000_000);
+    assert!(letters.levels.len() < 40);
+}